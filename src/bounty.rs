@@ -0,0 +1,75 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// Approve a bounty so the handle can claim it. Only the bounty's arbiter can approve
+    pub fn approve_bounty(&mut self, claim_id: ClaimId) {
+        let token_id = self
+            .claims_by_id
+            .get(&claim_id)
+            .unwrap_or_else(|| env::panic_str("Bounty not found"))
+            .claim_type
+            .token_id();
+        let default_expires_at = self.internal_expires_at(&token_id, None);
+        let claim = self.claims_by_id.get_mut(&claim_id).unwrap();
+        require!(
+            claim.status == ClaimStatus::AwaitingApproval,
+            "Bounty is not awaiting approval"
+        );
+        require!(
+            claim.arbiter.as_ref() == Some(&env::predecessor_account_id()),
+            "Only the arbiter can approve this bounty"
+        );
+        require!(!claim.is_expired(), "Bounty has expired");
+
+        // The handle gets at least the token's default claim period from approval,
+        // or the tipper's expiry when that's later
+        claim.status = ClaimStatus::Pending;
+        claim.expires_at = claim.expires_at.max(default_expires_at);
+
+        let social_handle = claim.social_handle();
+        log_bounty_approved_event(
+            claim_id,
+            &social_handle.platform,
+            &social_handle.handle,
+            &env::predecessor_account_id(),
+        );
+    }
+
+    /// Reject a bounty and refund the tipper. Only the bounty's arbiter can reject
+    pub fn reject_bounty(&mut self, claim_id: ClaimId) -> Promise {
+        let claim = self
+            .claims_by_id
+            .get_mut(&claim_id)
+            .unwrap_or_else(|| env::panic_str("Bounty not found"));
+        require!(
+            claim.status == ClaimStatus::AwaitingApproval,
+            "Bounty is not awaiting approval"
+        );
+        require!(
+            claim.arbiter.as_ref() == Some(&env::predecessor_account_id()),
+            "Only the arbiter can reject this bounty"
+        );
+
+        // Returns to `Rejected`, refundable through `reclaim_tip`, if the refund keeps failing
+        claim.status = ClaimStatus::Rejected;
+
+        let claim = claim.clone();
+        let social_handle = claim.social_handle();
+        log_bounty_rejected_event(
+            claim_id,
+            &social_handle.platform,
+            &social_handle.handle,
+            &env::predecessor_account_id(),
+        );
+
+        self.internal_transfer_claim(
+            social_handle,
+            claim_id,
+            claim.clone(),
+            claim.tipper().clone(),
+            "Refunded rejected bounty".to_string(),
+            SettlementKind::Reclaim,
+        )
+    }
+}
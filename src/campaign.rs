@@ -0,0 +1,354 @@
+use crate::*;
+
+pub type CampaignId = u64;
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CampaignMode {
+    /// Contributors can pull a refund if the goal isn't reached by the deadline
+    AllOrNothing,
+    /// The handle collects whatever was raised
+    KeepWhatYouRaise,
+}
+
+#[near(serializers=[json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CampaignStatus {
+    Active,
+    Succeeded,
+    Failed,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct Campaign {
+    pub handle: SocialHandle,
+    pub title: String,
+    pub goal: U128,
+    /// Token the goal is measured in, `near` for NEAR
+    pub goal_token: AccountId,
+    /// Tokens accepted as contributions, always including the goal token
+    pub accepted_tokens: Vec<AccountId>,
+    pub deadline: u64,
+    pub mode: CampaignMode,
+    /// Amount raised in the goal token
+    pub raised: U128,
+    pub created_at: u64,
+    /// Contributions already processed by `claim_campaign`
+    pub claim_cursor: u64,
+}
+
+/// Campaign settings passed to `create_campaign`
+#[near(serializers=[json])]
+pub struct NewCampaign {
+    pub title: String,
+    pub goal: U128,
+    /// Defaults to NEAR
+    pub goal_token: Option<AccountId>,
+    /// Defaults to the goal token alone
+    pub accepted_tokens: Option<Vec<AccountId>>,
+    pub deadline: u64,
+    pub mode: CampaignMode,
+}
+
+#[near(serializers=[json])]
+pub struct CampaignExternal {
+    pub id: CampaignId,
+    pub handle: SocialHandle,
+    pub title: String,
+    pub goal: U128,
+    pub goal_token: AccountId,
+    pub accepted_tokens: Vec<AccountId>,
+    pub deadline: u64,
+    pub mode: CampaignMode,
+    pub raised: U128,
+    pub created_at: u64,
+    pub status: CampaignStatus,
+}
+
+impl Campaign {
+    pub fn is_open(&self) -> bool {
+        env::block_timestamp() < self.deadline
+    }
+
+    pub fn status(&self) -> CampaignStatus {
+        if self.is_open() {
+            CampaignStatus::Active
+        } else if self.mode == CampaignMode::KeepWhatYouRaise || self.raised.0 >= self.goal.0 {
+            CampaignStatus::Succeeded
+        } else {
+            CampaignStatus::Failed
+        }
+    }
+}
+
+pub(crate) fn format_campaign(campaign_id: &CampaignId, campaign: &Campaign) -> CampaignExternal {
+    CampaignExternal {
+        id: *campaign_id,
+        handle: campaign.handle.clone(),
+        title: campaign.title.clone(),
+        goal: campaign.goal,
+        goal_token: campaign.goal_token.clone(),
+        accepted_tokens: campaign.accepted_tokens.clone(),
+        deadline: campaign.deadline,
+        mode: campaign.mode,
+        raised: campaign.raised,
+        created_at: campaign.created_at,
+        status: campaign.status(),
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Start a campaign for a linked handle. Only the linked account can create it
+    pub fn create_campaign(
+        &mut self,
+        platform: String,
+        handle: String,
+        campaign: NewCampaign,
+    ) -> CampaignId {
+        let NewCampaign {
+            title,
+            goal,
+            goal_token,
+            accepted_tokens,
+            deadline,
+            mode,
+        } = campaign;
+        let social_handle = SocialHandle::new(platform, handle);
+        require!(
+            self.linked_account(&social_handle) == Some(&env::predecessor_account_id()),
+            "Only the linked account can create a campaign"
+        );
+        require!(goal.0 > 0, "Goal must be greater than zero");
+        require!(
+            deadline > env::block_timestamp(),
+            "Deadline must be in the future"
+        );
+
+        let goal_token = goal_token.unwrap_or_else(near_token_id);
+        let mut accepted_tokens = accepted_tokens.unwrap_or_default();
+        if !accepted_tokens.contains(&goal_token) {
+            accepted_tokens.push(goal_token.clone());
+        }
+        for token_id in accepted_tokens.iter() {
+            require!(
+                *token_id == near_token_id()
+                    || self
+                        .supported_tokens
+                        .get(token_id)
+                        .is_some_and(|info| info.standard == TokenStandard::NEP141),
+                format!("Token {} can't be used for campaigns", token_id)
+            );
+        }
+
+        let campaign_id = self.next_campaign_id;
+        self.next_campaign_id += 1;
+        self.campaigns.insert(
+            campaign_id,
+            Campaign {
+                handle: social_handle.clone(),
+                title,
+                goal,
+                goal_token: goal_token.clone(),
+                accepted_tokens,
+                deadline,
+                mode,
+                raised: U128(0),
+                created_at: env::block_timestamp(),
+                claim_cursor: 0,
+            },
+        );
+
+        log_campaign_created_event(
+            campaign_id,
+            &social_handle.platform,
+            &social_handle.handle,
+            goal,
+            &goal_token,
+            deadline,
+        );
+
+        campaign_id
+    }
+
+    /// Collect the contributions of a successful campaign, up to `MAX_CLAIMS_PER_BATCH` per call.
+    /// Each call continues where the last one stopped; pass `cursor` to rescan from an earlier
+    /// contribution, e.g. one whose transfer was abandoned and is pending again
+    pub fn claim_campaign(&mut self, campaign_id: CampaignId, cursor: Option<u64>) {
+        self.assert_not_paused(Operation::Claim);
+
+        let campaign = self
+            .campaigns
+            .get(&campaign_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Campaign not found"));
+        // Contributions to a renamed handle are collected through its new handle
+        let (handle, claims_before) = self.resolve_handle(&campaign.handle);
+        self.assert_handle_not_blocked(&campaign.handle);
+        self.assert_handle_not_blocked(&handle);
+        require!(
+            !self.payouts_frozen(&handle),
+            "Payouts for this handle are frozen by a dispute"
+        );
+        require!(
+            !self.link_stale(&handle),
+            "Link has expired, refresh it with refresh_link"
+        );
+        let account_id = self
+            .linked_account(&handle)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Account must be linked before claiming."));
+        require!(
+            env::predecessor_account_id().eq(&account_id),
+            "Only the linked account can claim the campaign"
+        );
+        require!(
+            campaign.status() == CampaignStatus::Succeeded,
+            "Campaign has not succeeded"
+        );
+
+        if let Some(claim_ids) = self.campaign_claims.get(&campaign_id) {
+            let start = cursor.unwrap_or(campaign.claim_cursor);
+            let batch: Vec<ClaimId> = claim_ids
+                .iter()
+                .skip(start as usize)
+                .take(MAX_CLAIMS_PER_BATCH)
+                .cloned()
+                .collect();
+            let next_cursor = start + batch.len() as u64;
+            if let Some(campaign) = self.campaigns.get_mut(&campaign_id) {
+                campaign.claim_cursor = campaign.claim_cursor.max(next_cursor);
+            }
+
+            // Settled contributions are skipped, the cursor moves past them all the same
+            let claimable: Vec<ClaimId> = batch
+                .into_iter()
+                .filter(|claim_id| claims_before.is_none_or(|before| *claim_id < before))
+                .filter(|claim_id| {
+                    self.claims_by_id
+                        .get(claim_id)
+                        .is_some_and(|claim| claim.is_claimable())
+                })
+                .collect();
+            for claim_id in claimable {
+                if let Some(claim) = self.claims_by_id.get(&claim_id).cloned() {
+                    let memo = format!("Campaign contribution from {}", claim.public_tipper());
+                    self.internal_payout_claim(
+                        handle.clone(),
+                        claim_id,
+                        claim,
+                        account_id.clone(),
+                        memo,
+                    );
+                }
+            }
+        } else {
+            env::log_str("No contributions found for this campaign");
+        }
+    }
+
+    /// Refund a contribution to a failed all-or-nothing campaign
+    pub fn refund_contribution(&mut self, claim_id: ClaimId) -> Promise {
+        self.assert_not_paused(Operation::Reclaim);
+
+        let claim = self
+            .claims_by_id
+            .get(&claim_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Contribution not found"));
+        let campaign = claim
+            .campaign_id
+            .and_then(|campaign_id| self.campaigns.get(&campaign_id))
+            .unwrap_or_else(|| env::panic_str("Claim is not a campaign contribution"));
+        require!(
+            campaign.status() == CampaignStatus::Failed,
+            "Campaign has not failed"
+        );
+        require!(
+            claim.status == ClaimStatus::Pending,
+            "Contribution already settled"
+        );
+        require!(
+            &env::predecessor_account_id() == claim.tipper(),
+            "Only the contributor can request a refund"
+        );
+
+        self.internal_transfer_claim(
+            campaign.handle.clone(),
+            claim_id,
+            claim,
+            env::predecessor_account_id(),
+            "Refunded campaign contribution".to_string(),
+            SettlementKind::Reclaim,
+        )
+    }
+
+    pub fn get_campaign(&self, campaign_id: CampaignId) -> Option<CampaignExternal> {
+        self.campaigns
+            .get(&campaign_id)
+            .map(|campaign| format_campaign(&campaign_id, campaign))
+    }
+
+    pub fn get_campaigns(&self, cursor: Option<u64>, limit: u64) -> Page<CampaignExternal> {
+        paginate(
+            self.campaigns.iter(),
+            self.campaigns.len() as u64,
+            cursor,
+            limit,
+            |(campaign_id, campaign)| Some(format_campaign(campaign_id, campaign)),
+        )
+    }
+
+    pub fn get_campaign_contributions(
+        &self,
+        campaign_id: CampaignId,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Page<ClaimExternal> {
+        self.claims_page(
+            self.campaign_claims.get(&campaign_id),
+            cursor,
+            limit,
+            |claim_id, claim| Some(self.format_claim(claim_id, claim)),
+        )
+    }
+
+    // Internal helper to escrow a tip as a campaign contribution
+    pub(crate) fn internal_add_to_campaign(&mut self, campaign_id: CampaignId, claim: &mut Claim) {
+        let claim_period = self
+            .token_expiry(&claim.claim_type.token_id())
+            .default_period;
+        let campaign = self
+            .campaigns
+            .get_mut(&campaign_id)
+            .unwrap_or_else(|| env::panic_str("Campaign not found"));
+        require!(campaign.is_open(), "Campaign has ended");
+        require!(
+            claim.social_handle() == campaign.handle,
+            "Campaign belongs to a different handle"
+        );
+
+        let token_id = claim.claim_type.token_id();
+        require!(
+            campaign.accepted_tokens.contains(&token_id),
+            "Token not accepted by this campaign"
+        );
+        if token_id == campaign.goal_token {
+            campaign.raised = U128(campaign.raised.0 + claim.amount());
+        }
+
+        // The handle has the token's default claim period after the deadline to collect
+        claim.campaign_id = Some(campaign_id);
+        claim.expires_at = campaign.deadline.saturating_add(claim_period);
+
+        log_campaign_contribution_event(
+            campaign_id,
+            &campaign.handle.platform,
+            &campaign.handle.handle,
+            claim.amount().into(),
+            &token_id,
+            &claim.public_tipper(),
+        );
+    }
+}
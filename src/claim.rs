@@ -13,6 +13,51 @@ pub enum ClaimType {
     },
 }
 
+impl ClaimType {
+    /// Token account the claim is paid in, `near` for NEAR
+    pub fn token_id(&self) -> AccountId {
+        match self {
+            ClaimType::Near => near_token_id(),
+            ClaimType::FungibleToken { contract_id }
+            | ClaimType::NonFungibleToken { contract_id, .. } => contract_id.clone(),
+        }
+    }
+
+    /// Promise sending `amount` of the asset to `receiver_id`. NFTs ignore `amount`
+    pub fn transfer(&self, receiver_id: AccountId, amount: NearToken, memo: String) -> Promise {
+        match self {
+            ClaimType::Near => Promise::new(receiver_id).transfer(amount),
+            ClaimType::FungibleToken { contract_id } => external::ext_ft::ext(contract_id.clone())
+                .with_attached_deposit(NearToken::from_yoctonear(1))
+                .with_static_gas(GAS_FOR_FT_TRANSFER)
+                .ft_transfer(receiver_id, amount.as_yoctonear().to_string(), Some(memo)),
+            ClaimType::NonFungibleToken {
+                contract_id,
+                token_id,
+            } => external::ext_nft::ext(contract_id.clone())
+                .with_attached_deposit(NearToken::from_yoctonear(1))
+                .with_static_gas(GAS_FOR_NFT_TRANSFER)
+                .nft_transfer(receiver_id, token_id.clone(), None, Some(memo)),
+        }
+    }
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClaimStatus {
+    Pending,
+    /// Bounty waiting for its arbiter before it can be claimed
+    AwaitingApproval,
+    /// Bounty turned down by its arbiter, refundable to the tipper
+    Rejected,
+    Claimed,
+    Reclaimed,
+    /// Transfer in flight or waiting for `retry_settlement`
+    Settling,
+    /// Left unreclaimed past the grace period and sent to the sweep destination
+    Swept,
+}
+
 #[near(serializers=[borsh, json])]
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Claim {
@@ -22,23 +67,232 @@ pub struct Claim {
     pub recipient: String,
     pub timestamp: u64,
     pub expires_at: u64,
-    pub claimed: bool,
+    pub status: ClaimStatus,
+    /// Set for anonymous tips; shown instead of the tipper in views and events.
+    /// `tipper` is still stored, so anonymity does not extend to raw contract state
+    pub tipper_commitment: Option<String>,
+    /// Account that must approve a bounty before it becomes claimable
+    pub arbiter: Option<AccountId>,
+    /// Campaign the tip was contributed to, escrowed until the campaign ends
+    pub campaign_id: Option<CampaignId>,
+    /// Message from the tipper
+    pub memo: Option<String>,
+    /// Whether the tipper can cancel the tip before it expires
+    pub cancellable: bool,
+    /// Matching rounds the tip was counted towards when it was made. The contributions
+    /// are reversed if the tip goes back to the tipper instead of reaching the creator
+    #[serde(default)]
+    pub rounds: Vec<RoundId>,
+}
+
+/// Expiry chosen by the tipper, within the token's `ExpiryConfig`
+#[near(serializers=[json])]
+#[derive(Clone, Copy)]
+pub enum TipExpiry {
+    Never,
+    /// Nanoseconds from the time of the tip
+    After(u64),
 }
 
+/// Optional terms a tipper sets on a tip. FT and NFT tips take the same fields in `msg`
+#[near(serializers=[json])]
+#[derive(Clone, Default)]
+pub struct TipTerms {
+    /// Message shown to the creator on the claim
+    pub memo: Option<String>,
+    pub expiry: Option<TipExpiry>,
+    /// Allow cancelling the tip with `cancel_tip` before it expires
+    #[serde(default)]
+    pub cancellable: bool,
+}
+
+/// Asset of a claim with the token's registered metadata
+#[near(serializers=[json])]
+#[derive(Clone)]
+pub enum ClaimAsset {
+    Near {
+        symbol: String,
+        decimals: u8,
+    },
+    FungibleToken {
+        contract_id: AccountId,
+        /// None if the token is no longer registered
+        symbol: Option<String>,
+        decimals: Option<u8>,
+    },
+    NonFungibleToken {
+        contract_id: AccountId,
+        token_id: String,
+        symbol: Option<String>,
+    },
+}
+
+#[near(serializers=[json])]
+#[derive(Clone)]
+pub struct ClaimExternal {
+    pub id: ClaimId,
+    pub asset: ClaimAsset,
+    pub amount: U128,              // For NEAR and FTs. Ignored for NFTs
+    pub tipper: Option<AccountId>, // None for anonymous tips
+    pub tipper_commitment: Option<String>,
+    pub anonymous: bool,
+    pub recipient: String,
+    pub timestamp: u64,
+    pub expires_at: u64,
+    pub status: ClaimStatus,
+    pub memo: Option<String>,
+    /// Whether the tipper can take the claim back now
+    pub reclaimable: bool,
+    pub cancellable: bool,
+    pub arbiter: Option<AccountId>,
+    pub campaign_id: Option<CampaignId>,
+}
+
+/// Claim view served by the `_v1` methods, with the claim type flattened into a string
 #[near(serializers=[borsh, json])]
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct ClaimExternal {
+pub struct ClaimExternalV1 {
     pub id: ClaimId,
     pub claim_type: String,
-    pub amount: U128, // For NEAR and FTs. Ignored for NFTs
-    pub tipper: AccountId,
+    pub amount: U128,              // For NEAR and FTs. Ignored for NFTs
+    pub tipper: Option<AccountId>, // None for anonymous tips
+    pub tipper_commitment: Option<String>,
+    pub anonymous: bool,
     pub recipient: String,
     pub timestamp: u64,
     pub expires_at: u64,
     pub claimed: bool,
+    pub status: ClaimStatus,
+    pub arbiter: Option<AccountId>,
+    pub campaign_id: Option<CampaignId>,
 }
 
-pub(crate) fn format_claim(claim_id: &ClaimId, claim: &Claim) -> ClaimExternal {
+impl Contract {
+    pub(crate) fn format_claim(&self, claim_id: &ClaimId, claim: &Claim) -> ClaimExternal {
+        let token_info = |contract_id: &AccountId| self.supported_tokens.get(contract_id);
+        let asset = match &claim.claim_type {
+            ClaimType::Near => ClaimAsset::Near {
+                symbol: "NEAR".to_string(),
+                decimals: 24,
+            },
+            ClaimType::FungibleToken { contract_id } => ClaimAsset::FungibleToken {
+                contract_id: contract_id.clone(),
+                symbol: token_info(contract_id).map(|info| info.symbol.clone()),
+                decimals: token_info(contract_id).map(|info| info.decimals),
+            },
+            ClaimType::NonFungibleToken {
+                contract_id,
+                token_id,
+            } => ClaimAsset::NonFungibleToken {
+                contract_id: contract_id.clone(),
+                token_id: token_id.clone(),
+                symbol: token_info(contract_id).map(|info| info.symbol.clone()),
+            },
+        };
+        ClaimExternal {
+            id: *claim_id,
+            asset,
+            amount: claim.amount.as_yoctonear().into(),
+            tipper: if claim.is_anonymous() {
+                None
+            } else {
+                Some(claim.tipper.clone())
+            },
+            tipper_commitment: claim.tipper_commitment.clone(),
+            anonymous: claim.is_anonymous(),
+            recipient: claim.recipient.clone(),
+            timestamp: claim.timestamp,
+            expires_at: claim.expires_at,
+            status: claim.status,
+            memo: claim.memo.clone(),
+            reclaimable: claim.is_reclaimable(),
+            cancellable: claim.cancellable,
+            arbiter: claim.arbiter.clone(),
+            campaign_id: claim.campaign_id,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Push back the expiry of an unsettled tip to give the creator more time (tipper only)
+    pub fn extend_claim(&mut self, claim_id: ClaimId, new_expiry: u64) {
+        let claim = self
+            .claims_by_id
+            .get(&claim_id)
+            .unwrap_or_else(|| env::panic_str("Claim not found"));
+        require!(
+            &env::predecessor_account_id() == claim.tipper(),
+            "Only the original tipper can extend a tip"
+        );
+        require!(
+            matches!(
+                claim.status,
+                ClaimStatus::Pending | ClaimStatus::AwaitingApproval
+            ),
+            "Claim is already settled"
+        );
+        require!(
+            new_expiry > claim.expires_at,
+            "New expiry must be later than the current one"
+        );
+        let config = self.token_expiry(&claim.claim_type.token_id());
+        if new_expiry == NEVER_EXPIRES {
+            require!(
+                config.allow_never,
+                "Token doesn't allow tips that never expire"
+            );
+        } else {
+            require!(
+                env::block_timestamp()
+                    .checked_add(config.max_period)
+                    .is_none_or(|latest| new_expiry <= latest),
+                "Expiry is outside the token's bounds"
+            );
+        }
+
+        let claim = self.claims_by_id.get_mut(&claim_id).unwrap();
+        claim.expires_at = new_expiry;
+        log_claim_extended_event(claim_id, &claim.public_tipper(), new_expiry);
+    }
+
+    /// Take back a tip created as cancellable before it expires (tipper only)
+    pub fn cancel_tip(&mut self, claim_id: ClaimId) -> Promise {
+        self.assert_not_paused(Operation::Reclaim);
+        let claim = self
+            .claims_by_id
+            .get(&claim_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Claim not found"));
+        require!(
+            &env::predecessor_account_id() == claim.tipper(),
+            "Only the original tipper can cancel a tip"
+        );
+        require!(claim.cancellable, "Tip is not cancellable");
+        require!(
+            matches!(
+                claim.status,
+                ClaimStatus::Pending | ClaimStatus::AwaitingApproval
+            ),
+            "Claim is already settled"
+        );
+        require!(
+            !claim.is_expired(),
+            "Tip has expired, use reclaim_tip instead"
+        );
+
+        self.internal_transfer_claim(
+            claim.social_handle(),
+            claim_id,
+            claim,
+            env::predecessor_account_id(),
+            "Cancelled tip".to_string(),
+            SettlementKind::Cancel,
+        )
+    }
+}
+
+pub(crate) fn format_claim_v1(claim_id: &ClaimId, claim: &Claim) -> ClaimExternalV1 {
     let claim_type = match &claim.claim_type {
         ClaimType::Near => "Near".to_string(),
         ClaimType::FungibleToken { contract_id } => format!("FT({})", contract_id),
@@ -47,15 +301,24 @@ pub(crate) fn format_claim(claim_id: &ClaimId, claim: &Claim) -> ClaimExternal {
             token_id,
         } => format!("NFT({}, {})", contract_id, token_id),
     };
-    ClaimExternal {
-        id: claim_id.clone(),
+    ClaimExternalV1 {
+        id: *claim_id,
         claim_type,
         amount: claim.amount.as_yoctonear().into(),
-        tipper: claim.tipper.clone(),
+        tipper: if claim.is_anonymous() {
+            None
+        } else {
+            Some(claim.tipper.clone())
+        },
+        tipper_commitment: claim.tipper_commitment.clone(),
+        anonymous: claim.is_anonymous(),
         recipient: claim.recipient.clone(),
         timestamp: claim.timestamp,
         expires_at: claim.expires_at,
-        claimed: claim.claimed,
+        claimed: claim.status == ClaimStatus::Claimed,
+        status: claim.status,
+        arbiter: claim.arbiter.clone(),
+        campaign_id: claim.campaign_id,
     }
 }
 
@@ -68,7 +331,13 @@ impl Claim {
             recipient,
             timestamp: env::block_timestamp(),
             expires_at: env::block_timestamp() + crate::CLAIM_EXPIRATION_PERIOD,
-            claimed: false,
+            status: ClaimStatus::Pending,
+            tipper_commitment: None,
+            arbiter: None,
+            campaign_id: None,
+            memo: None,
+            cancellable: false,
+            rounds: vec![],
         }
     }
 
@@ -85,7 +354,13 @@ impl Claim {
             recipient,
             timestamp: env::block_timestamp(),
             expires_at: env::block_timestamp() + crate::CLAIM_EXPIRATION_PERIOD,
-            claimed: false,
+            status: ClaimStatus::Pending,
+            tipper_commitment: None,
+            arbiter: None,
+            campaign_id: None,
+            memo: None,
+            cancellable: false,
+            rounds: vec![],
         }
     }
 
@@ -105,14 +380,43 @@ impl Claim {
             recipient,
             timestamp: env::block_timestamp(),
             expires_at: env::block_timestamp() + crate::CLAIM_EXPIRATION_PERIOD,
-            claimed: false,
+            status: ClaimStatus::Pending,
+            tipper_commitment: None,
+            arbiter: None,
+            campaign_id: None,
+            memo: None,
+            cancellable: false,
+            rounds: vec![],
         }
     }
 
+    /// Turn the claim into a bounty that `arbiter` must approve
+    pub fn set_arbiter(&mut self, arbiter: AccountId) {
+        self.status = ClaimStatus::AwaitingApproval;
+        self.arbiter = Some(arbiter);
+    }
+
     pub fn is_expired(&self) -> bool {
         env::block_timestamp() >= self.expires_at
     }
 
+    /// Pending and not yet expired, so the linked account can claim it
+    pub fn is_claimable(&self) -> bool {
+        self.status == ClaimStatus::Pending && !self.is_expired()
+    }
+
+    /// Expired without being claimed or approved, or rejected by the arbiter
+    pub fn is_reclaimable(&self) -> bool {
+        match self.status {
+            ClaimStatus::Pending | ClaimStatus::AwaitingApproval => self.is_expired(),
+            ClaimStatus::Rejected => true,
+            ClaimStatus::Claimed
+            | ClaimStatus::Reclaimed
+            | ClaimStatus::Settling
+            | ClaimStatus::Swept => false,
+        }
+    }
+
     pub fn amount(&self) -> u128 {
         self.amount.as_yoctonear()
     }
@@ -121,6 +425,38 @@ impl Claim {
         &self.tipper
     }
 
+    pub fn set_memo(&mut self, memo: Option<String>) {
+        if let Some(memo) = &memo {
+            require!(memo.len() <= MAX_MEMO_LENGTH, "Memo is too long");
+        }
+        self.memo = memo;
+    }
+
+    /// Hide the tipper behind a commitment hash in views and events. This is an API-level
+    /// mask only, the tipper remains visible in the transaction and in contract state
+    pub fn set_anonymous(&mut self) {
+        let mut preimage = self.tipper.as_bytes().to_vec();
+        preimage.extend_from_slice(&env::random_seed());
+        self.tipper_commitment = Some(bs58::encode(env::sha256(&preimage)).into_string());
+    }
+
+    pub fn is_anonymous(&self) -> bool {
+        self.tipper_commitment.is_some()
+    }
+
+    /// Tipper as it may be shown publicly: the commitment hash for anonymous tips
+    pub fn public_tipper(&self) -> String {
+        match &self.tipper_commitment {
+            Some(commitment) => commitment.clone(),
+            None => self.tipper.to_string(),
+        }
+    }
+
+    pub fn social_handle(&self) -> SocialHandle {
+        let (platform, handle) = self.recipient.split_once(':').unwrap_or_default();
+        SocialHandle::new(platform.to_string(), handle.to_string())
+    }
+
     pub fn token_type(&self) -> &str {
         match &self.claim_type {
             ClaimType::Near => "NEAR",
@@ -0,0 +1,174 @@
+use crate::*;
+
+/// A challenge to the account a handle is linked to
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct Dispute {
+    pub challenger: AccountId,
+    /// Account the handle was linked to when the dispute was opened
+    pub linked_account: AccountId,
+    pub bond: U128,
+    /// The challenger's verified proof
+    pub proof: LinkProof,
+    pub opened_at: u64,
+    /// Payouts for the handle are frozen until this time, or until resolved
+    pub review_ends_at: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Challenge a linked handle with a fresh proof of ownership. Requires `DISPUTE_BOND`,
+    /// returned or slashed when an arbiter resolves the dispute
+    #[payable]
+    pub fn dispute_link(
+        &mut self,
+        platform: String,
+        handle: String,
+        proof: ReclaimProof,
+    ) -> Promise {
+        self.assert_not_paused(Operation::Link);
+        let bond = env::attached_deposit();
+        require!(bond >= DISPUTE_BOND, "Requires dispute bond");
+
+        let social_handle = SocialHandle::new(platform, handle);
+        let record = self
+            .links
+            .get(&social_handle)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Handle is not linked"));
+        let linked_account = record.account_id;
+        require!(
+            linked_account != env::predecessor_account_id(),
+            "Handle is already linked to this account"
+        );
+        require!(
+            !self.disputes.contains_key(&social_handle.to_string()),
+            "Handle already has an open dispute"
+        );
+        require!(proof.is_recent(env::block_timestamp()), "Proof is too old");
+        // A replay of the proof the link was verified with can't challenge it
+        require!(
+            proof.signedClaim.claim.identifier != record.proof_identifier
+                && proof
+                    .signedClaim
+                    .claim
+                    .timestampS
+                    .saturating_mul(1_000_000_000)
+                    > record.verified_at,
+            "Proof must be newer than the link's verification"
+        );
+        proof.assert_handle(&social_handle.handle);
+
+        let now = env::block_timestamp();
+        let dispute = Dispute {
+            challenger: env::predecessor_account_id(),
+            linked_account,
+            bond: U128(bond.as_yoctonear()),
+            proof: LinkProof::from_proof(&proof),
+            opened_at: now,
+            review_ends_at: now + DISPUTE_REVIEW_PERIOD,
+        };
+        external::ext_reclaim::ext(self.reclaim_contract_id.clone())
+            .with_static_gas(GAS_FOR_RECLAIM_VERIFY)
+            .verify_proof(proof)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas::from_tgas(10))
+                    .on_dispute_proof_verified(social_handle, dispute),
+            )
+    }
+
+    #[private]
+    pub fn on_dispute_proof_verified(
+        &mut self,
+        social_handle: SocialHandle,
+        dispute: Dispute,
+        #[callback_result] verification_result: Result<(), PromiseError>,
+    ) {
+        if verification_result.is_err() || self.disputes.contains_key(&social_handle.to_string()) {
+            env::log_str("Dispute rejected, returning bond");
+            Promise::new(dispute.challenger).transfer(NearToken::from_yoctonear(dispute.bond.0));
+            return;
+        }
+
+        self.internal_escrow(&near_token_id(), dispute.bond.0);
+        log_link_disputed_event(
+            &social_handle.platform,
+            &social_handle.handle,
+            &dispute.challenger,
+            &dispute.linked_account,
+            dispute.bond,
+        );
+        self.disputes.insert(social_handle.to_string(), dispute);
+    }
+
+    /// Resolve a dispute (arbiter only). `move_link` links the handle to the challenger,
+    /// `slash_bond` sends the bond to the owner instead of returning it
+    pub fn resolve_dispute(
+        &mut self,
+        platform: String,
+        handle: String,
+        move_link: bool,
+        slash_bond: bool,
+    ) {
+        self.assert_role(Role::Arbiter);
+        let social_handle = SocialHandle::new(platform, handle);
+        let dispute = self
+            .disputes
+            .remove(&social_handle.to_string())
+            .unwrap_or_else(|| env::panic_str("No open dispute for this handle"));
+
+        if move_link {
+            self.internal_link(
+                &social_handle,
+                dispute.challenger.clone(),
+                dispute.proof.clone(),
+            );
+            log_account_linked_event(
+                &social_handle.platform,
+                &social_handle.handle,
+                &dispute.challenger,
+            );
+        }
+
+        self.internal_release(&near_token_id(), dispute.bond.0);
+        let bond_receiver = if slash_bond {
+            self.owner_id.clone()
+        } else {
+            dispute.challenger.clone()
+        };
+        Promise::new(bond_receiver).transfer(NearToken::from_yoctonear(dispute.bond.0));
+
+        log_dispute_resolved_event(
+            &social_handle.platform,
+            &social_handle.handle,
+            &dispute.challenger,
+            move_link,
+            slash_bond,
+            &env::predecessor_account_id(),
+        );
+    }
+
+    pub fn get_dispute(&self, platform: String, handle: String) -> Option<Dispute> {
+        self.disputes
+            .get(&SocialHandle::new(platform, handle).to_string())
+            .cloned()
+    }
+
+    pub fn get_disputes(
+        &self,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Page<(String, Dispute), String> {
+        paginate_keys(&self.disputes, cursor, limit, |handle, dispute| {
+            Some((handle.clone(), dispute.clone()))
+        })
+    }
+
+    /// Whether a dispute is holding the handle's payouts
+    pub(crate) fn payouts_frozen(&self, social_handle: &SocialHandle) -> bool {
+        self.disputes
+            .get(&social_handle.to_string())
+            .is_some_and(|dispute| env::block_timestamp() < dispute.review_ends_at)
+    }
+}
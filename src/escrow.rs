@@ -0,0 +1,242 @@
+use crate::*;
+
+/// A token held by the contract compared to what it owes
+#[near(serializers=[json])]
+pub struct SolvencyReport {
+    /// `near` for NEAR
+    pub token_id: AccountId,
+    /// Amount owed to pending claims and open round pools
+    pub escrowed: U128,
+    /// Balance held by the contract, excluding NEAR reserved for storage
+    pub held: U128,
+    /// Amount the guardian can rescue
+    pub surplus: U128,
+    /// Amount missing to cover the escrow
+    pub deficit: U128,
+    pub solvent: bool,
+}
+
+impl SolvencyReport {
+    fn new(token_id: AccountId, escrowed: u128, held: u128) -> Self {
+        Self {
+            token_id,
+            escrowed: escrowed.into(),
+            held: held.into(),
+            surplus: held.saturating_sub(escrowed).into(),
+            deficit: escrowed.saturating_sub(held).into(),
+            solvent: held >= escrowed,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Send NEAR that isn't backed by a claim or round pool to `receiver_id` (guardian only)
+    pub fn rescue_near(&mut self, amount: U128, receiver_id: AccountId) -> Promise {
+        self.assert_role(Role::Guardian);
+        require!(
+            amount.0 <= self.get_escrow_invariant().surplus.0,
+            "Amount is backed by escrow"
+        );
+
+        log_assets_rescued_event(
+            &near_token_id(),
+            None,
+            amount,
+            &receiver_id,
+            &env::predecessor_account_id(),
+        );
+        Promise::new(receiver_id).transfer(NearToken::from_yoctonear(amount.0))
+    }
+
+    /// Send FT that isn't backed by a claim or round pool to `receiver_id` (guardian only).
+    /// The contract's balance is read from the token before transferring
+    pub fn rescue_ft(
+        &mut self,
+        token_id: AccountId,
+        amount: U128,
+        receiver_id: AccountId,
+    ) -> Promise {
+        self.assert_role(Role::Guardian);
+
+        external::ext_ft::ext(token_id.clone())
+            .with_static_gas(GAS_FOR_FT_TRANSFER)
+            .ft_balance_of(env::current_account_id())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas::from_tgas(20))
+                    .on_rescue_ft_balance(
+                        token_id,
+                        amount,
+                        receiver_id,
+                        env::predecessor_account_id(),
+                    ),
+            )
+    }
+
+    #[private]
+    pub fn on_rescue_ft_balance(
+        &mut self,
+        token_id: AccountId,
+        amount: U128,
+        receiver_id: AccountId,
+        rescued_by: AccountId,
+        #[callback_result] balance: Result<U128, PromiseError>,
+    ) -> Promise {
+        let balance = balance.unwrap_or_else(|_| env::panic_str("Failed to read token balance"));
+        let surplus = balance.0.saturating_sub(self.escrowed_amount(&token_id));
+        require!(amount.0 <= surplus, "Amount is backed by escrow");
+
+        log_assets_rescued_event(&token_id, None, amount, &receiver_id, &rescued_by);
+        external::ext_ft::ext(token_id)
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .with_static_gas(GAS_FOR_FT_TRANSFER)
+            .ft_transfer(
+                receiver_id,
+                amount.0.to_string(),
+                Some("Rescued by guardian".to_string()),
+            )
+    }
+
+    /// Send an NFT that isn't held for a claim to `receiver_id` (guardian only)
+    pub fn rescue_nft(
+        &mut self,
+        contract_id: AccountId,
+        token_id: String,
+        receiver_id: AccountId,
+    ) -> Promise {
+        self.assert_role(Role::Guardian);
+        require!(
+            !self
+                .escrowed_nfts
+                .contains(&(contract_id.clone(), token_id.clone())),
+            "NFT is backed by a claim"
+        );
+
+        log_assets_rescued_event(
+            &contract_id,
+            Some(&token_id),
+            U128(1),
+            &receiver_id,
+            &env::predecessor_account_id(),
+        );
+        external::ext_nft::ext(contract_id)
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .with_static_gas(GAS_FOR_NFT_TRANSFER)
+            .nft_transfer(
+                receiver_id,
+                token_id,
+                None,
+                Some("Rescued by guardian".to_string()),
+            )
+    }
+
+    /// Amount of a token owed to pending claims and open round pools, `near` for NEAR
+    pub fn get_escrowed(&self, token_id: AccountId) -> U128 {
+        self.escrowed_amount(&token_id).into()
+    }
+
+    pub fn is_nft_escrowed(&self, contract_id: AccountId, token_id: String) -> bool {
+        self.escrowed_nfts.contains(&(contract_id, token_id))
+    }
+
+    /// Compare escrowed NEAR to the contract's balance
+    pub fn get_escrow_invariant(&self) -> SolvencyReport {
+        let storage_cost = env::storage_byte_cost()
+            .as_yoctonear()
+            .saturating_mul(env::storage_usage() as u128);
+        let held = env::account_balance()
+            .as_yoctonear()
+            .saturating_sub(storage_cost);
+
+        SolvencyReport::new(
+            near_token_id(),
+            self.escrowed_amount(&near_token_id()),
+            held,
+        )
+    }
+
+    /// Report escrowed vs. held for a token, NEAR when omitted. FT balances are read with
+    /// `ft_balance_of`, so FT audits must be sent as a transaction. Deficits are logged as events
+    pub fn audit_solvency(&self, token_id: Option<AccountId>) -> PromiseOrValue<SolvencyReport> {
+        let token_id = token_id.unwrap_or_else(near_token_id);
+        if token_id == near_token_id() {
+            let report = self.get_escrow_invariant();
+            if !report.solvent {
+                log_escrow_deficit_event(&report.token_id, report.escrowed, report.held);
+            }
+            return PromiseOrValue::Value(report);
+        }
+
+        PromiseOrValue::Promise(
+            external::ext_ft::ext(token_id.clone())
+                .with_static_gas(GAS_FOR_FT_TRANSFER)
+                .ft_balance_of(env::current_account_id())
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(Gas::from_tgas(5))
+                        .on_audit_ft_balance(token_id),
+                ),
+        )
+    }
+
+    #[private]
+    pub fn on_audit_ft_balance(
+        &self,
+        token_id: AccountId,
+        #[callback_result] balance: Result<U128, PromiseError>,
+    ) -> SolvencyReport {
+        let balance = balance.unwrap_or_else(|_| env::panic_str("Failed to read token balance"));
+        let report =
+            SolvencyReport::new(token_id.clone(), self.escrowed_amount(&token_id), balance.0);
+        if !report.solvent {
+            log_escrow_deficit_event(&report.token_id, report.escrowed, report.held);
+        }
+        report
+    }
+
+    fn escrowed_amount(&self, token_id: &AccountId) -> u128 {
+        self.escrow_totals.get(token_id).copied().unwrap_or(0)
+    }
+
+    // Internal helper to count a new claim's asset as escrowed
+    pub(crate) fn internal_escrow_claim(&mut self, claim: &Claim) {
+        match &claim.claim_type {
+            ClaimType::NonFungibleToken {
+                contract_id,
+                token_id,
+            } => {
+                self.escrowed_nfts
+                    .insert((contract_id.clone(), token_id.clone()));
+            }
+            claim_type => self.internal_escrow(&claim_type.token_id(), claim.amount()),
+        }
+    }
+
+    // Internal helper to release `amount` of a claim's asset once it left the contract
+    pub(crate) fn internal_release_claim(&mut self, claim: &Claim, amount: u128) {
+        match &claim.claim_type {
+            ClaimType::NonFungibleToken {
+                contract_id,
+                token_id,
+            } => {
+                self.escrowed_nfts
+                    .remove(&(contract_id.clone(), token_id.clone()));
+            }
+            claim_type => self.internal_release(&claim_type.token_id(), amount),
+        }
+    }
+
+    pub(crate) fn internal_escrow(&mut self, token_id: &AccountId, amount: u128) {
+        *self.escrow_totals.entry(token_id.clone()).or_insert(0) += amount;
+    }
+
+    pub(crate) fn internal_release(&mut self, token_id: &AccountId, amount: u128) {
+        let total = self.escrow_totals.entry(token_id.clone()).or_insert(0);
+        if amount > *total {
+            // Releasing more than was escrowed means a settlement path skipped the books
+            log_escrow_mismatch_event(token_id, U128(*total), amount.into());
+        }
+        *total = total.saturating_sub(amount);
+    }
+}
@@ -61,7 +61,7 @@ pub fn log_claim_created_event(
     handle: &str,
     amount: U128,
     token_type: &str,
-    tipper: &AccountId,
+    tipper: &str,
 ) {
     env::log_str(
         format!(
@@ -121,7 +121,7 @@ pub fn log_tip_reclaimed_event(
     handle: &str,
     amount: U128,
     token_type: &str,
-    tipper: &AccountId,
+    tipper: &str,
 ) {
     env::log_str(
         format!(
@@ -145,6 +145,976 @@ pub fn log_tip_reclaimed_event(
         .as_ref(),
     );
 }
+pub fn log_bounty_created_event(
+    claim_id: ClaimId,
+    platform: &str,
+    handle: &str,
+    arbiter: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "bounty_created",
+                "data": [
+                    {
+                        "claim_id": claim_id,
+                        "platform": platform,
+                        "handle": handle,
+                        "arbiter": arbiter,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_bounty_approved_event(
+    claim_id: ClaimId,
+    platform: &str,
+    handle: &str,
+    arbiter: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "bounty_approved",
+                "data": [
+                    {
+                        "claim_id": claim_id,
+                        "platform": platform,
+                        "handle": handle,
+                        "arbiter": arbiter,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_bounty_rejected_event(
+    claim_id: ClaimId,
+    platform: &str,
+    handle: &str,
+    arbiter: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "bounty_rejected",
+                "data": [
+                    {
+                        "claim_id": claim_id,
+                        "platform": platform,
+                        "handle": handle,
+                        "arbiter": arbiter,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+pub fn log_campaign_created_event(
+    campaign_id: CampaignId,
+    platform: &str,
+    handle: &str,
+    goal: U128,
+    goal_token: &AccountId,
+    deadline: u64,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "campaign_created",
+                "data": [
+                    {
+                        "campaign_id": campaign_id,
+                        "platform": platform,
+                        "handle": handle,
+                        "goal": goal,
+                        "goal_token": goal_token,
+                        "deadline": deadline,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_campaign_contribution_event(
+    campaign_id: CampaignId,
+    platform: &str,
+    handle: &str,
+    amount: U128,
+    token_id: &AccountId,
+    tipper: &str,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "campaign_contribution",
+                "data": [
+                    {
+                        "campaign_id": campaign_id,
+                        "platform": platform,
+                        "handle": handle,
+                        "amount": amount,
+                        "token_id": token_id,
+                        "tipper": tipper,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+pub fn log_round_created_event(
+    round_id: RoundId,
+    token_id: &AccountId,
+    start: u64,
+    end: u64,
+    matching_pool: U128,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "round_created",
+                "data": [
+                    {
+                        "round_id": round_id,
+                        "token_id": token_id,
+                        "start": start,
+                        "end": end,
+                        "matching_pool": matching_pool,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_round_funded_event(round_id: RoundId, funder: &AccountId, amount: U128) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "round_funded",
+                "data": [
+                    {
+                        "round_id": round_id,
+                        "funder": funder,
+                        "amount": amount,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_round_finalized_event(round_id: RoundId, distributed: U128, returned: U128) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "round_finalized",
+                "data": [
+                    {
+                        "round_id": round_id,
+                        "distributed": distributed,
+                        "returned": returned,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_tip_forwarded_event(
+    platform: &str,
+    handle: &str,
+    amount: U128,
+    token_type: &str,
+    recipient: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "tip_forwarded",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "amount": amount,
+                        "token_type": token_type,
+                        "recipient": recipient,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_tip_route_updated_event(platform: &str, handle: &str, route: &Option<TipRoute>) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "tip_route_updated",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "route": route,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_role_granted_event(role: Role, account_id: &AccountId, granted_by: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "role_granted",
+                "data": [
+                    {
+                        "role": role,
+                        "account_id": account_id,
+                        "granted_by": granted_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_role_revoked_event(role: Role, account_id: &AccountId, revoked_by: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "role_revoked",
+                "data": [
+                    {
+                        "role": role,
+                        "account_id": account_id,
+                        "revoked_by": revoked_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_ownership_proposed_event(
+    owner_id: &AccountId,
+    proposed_owner: &AccountId,
+    accept_after: u64,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "ownership_proposed",
+                "data": [
+                    {
+                        "owner_id": owner_id,
+                        "proposed_owner": proposed_owner,
+                        "accept_after": accept_after,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_ownership_transferred_event(previous_owner: &AccountId, new_owner: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "ownership_transferred",
+                "data": [
+                    {
+                        "previous_owner": previous_owner,
+                        "new_owner": new_owner,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_ownership_transfer_cancelled_event(owner_id: &AccountId, proposed_owner: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "ownership_transfer_cancelled",
+                "data": [
+                    {
+                        "owner_id": owner_id,
+                        "proposed_owner": proposed_owner,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_admin_action_queued_event(
+    action_id: ActionId,
+    action: &AdminAction,
+    executable_at: u64,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "admin_action_queued",
+                "data": [
+                    {
+                        "action_id": action_id,
+                        "action": action,
+                        "executable_at": executable_at,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_admin_action_executed_event(action_id: ActionId, action: &AdminAction) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "admin_action_executed",
+                "data": [
+                    {
+                        "action_id": action_id,
+                        "action": action,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_admin_action_cancelled_event(
+    action_id: ActionId,
+    action: &AdminAction,
+    cancelled_by: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "admin_action_cancelled",
+                "data": [
+                    {
+                        "action_id": action_id,
+                        "action": action,
+                        "cancelled_by": cancelled_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_operations_paused_event(operations: &[Operation], paused_by: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "operations_paused",
+                "data": [
+                    {
+                        "operations": operations,
+                        "paused_by": paused_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_operations_unpaused_event(operations: &[Operation], unpaused_by: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "operations_unpaused",
+                "data": [
+                    {
+                        "operations": operations,
+                        "unpaused_by": unpaused_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_token_paused_event(token_id: &AccountId, paused: bool, updated_by: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "token_paused",
+                "data": [
+                    {
+                        "token_id": token_id,
+                        "paused": paused,
+                        "updated_by": updated_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_assets_rescued_event(
+    token_id: &AccountId,
+    nft_token_id: Option<&str>,
+    amount: U128,
+    receiver_id: &AccountId,
+    rescued_by: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "assets_rescued",
+                "data": [
+                    {
+                        "token_id": token_id,
+                        "nft_token_id": nft_token_id,
+                        "amount": amount,
+                        "receiver_id": receiver_id,
+                        "rescued_by": rescued_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_escrow_deficit_event(token_id: &AccountId, escrowed: U128, held: U128) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "escrow_deficit",
+                "data": [
+                    {
+                        "token_id": token_id,
+                        "escrowed": escrowed,
+                        "held": held,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_escrow_mismatch_event(token_id: &AccountId, escrowed: U128, released: U128) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "escrow_mismatch",
+                "data": [
+                    {
+                        "token_id": token_id,
+                        "escrowed": escrowed,
+                        "released": released,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_handle_blocked_event(platform: &str, handle: &str, reason: &str, moderator: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "handle_blocked",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "reason": reason,
+                        "moderator": moderator,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_handle_unblocked_event(
+    platform: &str,
+    handle: &str,
+    reason: &str,
+    moderator: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "handle_unblocked",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "reason": reason,
+                        "moderator": moderator,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_account_blocked_event(account_id: &AccountId, reason: &str, moderator: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "account_blocked",
+                "data": [
+                    {
+                        "account_id": account_id,
+                        "reason": reason,
+                        "moderator": moderator,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_account_unblocked_event(account_id: &AccountId, reason: &str, moderator: &AccountId) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "account_unblocked",
+                "data": [
+                    {
+                        "account_id": account_id,
+                        "reason": reason,
+                        "moderator": moderator,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_link_disputed_event(
+    platform: &str,
+    handle: &str,
+    challenger: &AccountId,
+    linked_account: &AccountId,
+    bond: U128,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "link_disputed",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "challenger": challenger,
+                        "linked_account": linked_account,
+                        "bond": bond,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_dispute_resolved_event(
+    platform: &str,
+    handle: &str,
+    challenger: &AccountId,
+    link_moved: bool,
+    bond_slashed: bool,
+    arbiter: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "dispute_resolved",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "challenger": challenger,
+                        "link_moved": link_moved,
+                        "bond_slashed": bond_slashed,
+                        "arbiter": arbiter,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_link_refreshed_event(
+    platform: &str,
+    handle: &str,
+    account_id: &AccountId,
+    expires_at: u64,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "link_refreshed",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "account_id": account_id,
+                        "expires_at": expires_at,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_handle_renamed_event(
+    platform: &str,
+    old_handle: &str,
+    new_handle: &str,
+    account_id: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "handle_renamed",
+                "data": [
+                    {
+                        "platform": platform,
+                        "old_handle": old_handle,
+                        "new_handle": new_handle,
+                        "account_id": account_id,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_token_expiry_updated_event(
+    token_id: &AccountId,
+    expiry: &ExpiryConfig,
+    updated_by: &AccountId,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "token_expiry_updated",
+                "data": [
+                    {
+                        "token_id": token_id,
+                        "expiry": expiry,
+                        "updated_by": updated_by,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_claim_extended_event(claim_id: ClaimId, tipper: &str, expires_at: u64) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "claim_extended",
+                "data": [
+                    {
+                        "claim_id": claim_id,
+                        "tipper": tipper,
+                        "expires_at": expires_at,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_tip_cancelled_event(
+    platform: &str,
+    handle: &str,
+    claim_id: ClaimId,
+    amount: U128,
+    token_type: &str,
+    tipper: &str,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "tip_cancelled",
+                "data": [
+                    {
+                        "platform": platform,
+                        "handle": handle,
+                        "claim_id": claim_id,
+                        "amount": amount,
+                        "token_type": token_type,
+                        "tipper": tipper,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_claim_swept_event(
+    claim_id: ClaimId,
+    amount: U128,
+    token_type: &str,
+    destination: &str,
+    caller: &AccountId,
+    incentive: U128,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "claim_swept",
+                "data": [
+                    {
+                        "claim_id": claim_id,
+                        "amount": amount,
+                        "token_type": token_type,
+                        "destination": destination,
+                        "caller": caller,
+                        "incentive": incentive,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_settlement_failed_event(
+    claim_id: ClaimId,
+    beneficiary: &str,
+    reason: &str,
+    failed_attempts: u32,
+) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "settlement_failed",
+                "data": [
+                    {
+                        "claim_id": claim_id,
+                        "beneficiary": beneficiary,
+                        "reason": reason,
+                        "failed_attempts": failed_attempts,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
+pub fn log_settlement_abandoned_event(claim_id: ClaimId, beneficiary: &str, reason: &str) {
+    env::log_str(
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            json!({
+                "standard": "claim_protocol",
+                "version": "1.0.0",
+                "event": "settlement_abandoned",
+                "data": [
+                    {
+                        "claim_id": claim_id,
+                        "beneficiary": beneficiary,
+                        "reason": reason,
+                    }
+                ]
+            })
+        )
+        .as_ref(),
+    );
+}
+
 // use near_sdk::{AccountId, log};
 // use near_sdk::serde_json::json;
 
@@ -1,13 +1,18 @@
+use near_sdk::json_types::U128;
 use near_sdk::{ext_contract, AccountId, Promise};
 
+#[allow(dead_code)]
 #[ext_contract(ext_reclaim)]
 pub trait ReclaimProtocol {
     #[handle_result]
     fn verify_proof(proof: crate::proof::ReclaimProof) -> Result<(), &'static str>;
 }
 
+#[allow(dead_code)]
 #[ext_contract(ext_ft)]
 pub trait FungibleToken {
+    fn ft_balance_of(account_id: AccountId) -> U128;
+
     fn ft_transfer(receiver_id: AccountId, amount: String, memo: Option<String>);
 
     fn ft_transfer_call(
@@ -18,6 +23,7 @@ pub trait FungibleToken {
     ) -> Promise;
 }
 
+#[allow(dead_code)]
 #[ext_contract(ext_nft)]
 pub trait NonFungibleToken {
     fn nft_transfer(
@@ -35,3 +41,30 @@ pub trait NonFungibleToken {
         msg: String,
     ) -> Promise;
 }
+
+/// PotLock pot: NEAR donations to a registered project
+#[allow(dead_code)]
+#[ext_contract(ext_pot)]
+pub trait PotLockPot {
+    fn donate(
+        message: Option<String>,
+        referrer_id: Option<AccountId>,
+        project_id: Option<AccountId>,
+        matching_pool: Option<bool>,
+        bypass_protocol_fee: Option<bool>,
+        custom_chef_fee_basis_points: Option<u32>,
+    ) -> Promise;
+}
+
+/// PotLock donation contract: direct NEAR donations to any account.
+/// FT donations go through `ft_transfer_call` with `{"recipient_id": ...}` as msg
+#[allow(dead_code)]
+#[ext_contract(ext_donation)]
+pub trait PotLockDonation {
+    fn donate(
+        recipient_id: AccountId,
+        message: Option<String>,
+        referrer_id: Option<AccountId>,
+        bypass_protocol_fee: Option<bool>,
+    ) -> Promise;
+}
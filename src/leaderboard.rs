@@ -0,0 +1,142 @@
+use crate::*;
+
+/// Default number of entries kept on each leaderboard
+pub const DEFAULT_LEADERBOARD_SIZE: u32 = 10;
+/// Upper bound for the leaderboard size, keeping tip updates cheap
+const MAX_LEADERBOARD_SIZE: u32 = 100;
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LeaderboardKey {
+    /// Most tipped handles of a platform in a token
+    Handles {
+        platform: String,
+        token_id: AccountId,
+    },
+    /// Most generous tippers in a token
+    Tippers { token_id: AccountId },
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct LeaderboardEntry {
+    /// "platform:handle" for handles, the account id for tippers
+    pub id: String,
+    pub total: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Set how many entries each leaderboard keeps (owner only). Boards shrink or
+    /// grow as they are next updated
+    pub fn set_leaderboard_size(&mut self, size: u32) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only owner can change the leaderboard size"
+        );
+        require!(
+            size > 0 && size <= MAX_LEADERBOARD_SIZE,
+            format!("Size must be between 1 and {}", MAX_LEADERBOARD_SIZE)
+        );
+        self.leaderboard_size = size;
+
+        env::log_str(&format!("Leaderboard size changed to {}", size));
+    }
+
+    pub fn get_leaderboard_size(&self) -> u32 {
+        self.leaderboard_size
+    }
+
+    /// Most tipped handles of a platform in a token, NEAR when omitted
+    pub fn get_top_handles(
+        &self,
+        platform: String,
+        token_id: Option<AccountId>,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Page<LeaderboardEntry> {
+        self.leaderboard_page(
+            &LeaderboardKey::Handles {
+                platform: platform.to_lowercase(),
+                token_id: token_id.unwrap_or_else(near_token_id),
+            },
+            cursor,
+            limit,
+        )
+    }
+
+    /// Accounts that tipped the most in a token, NEAR when omitted. Anonymous tips are left out
+    pub fn get_top_tippers(
+        &self,
+        token_id: Option<AccountId>,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Page<LeaderboardEntry> {
+        self.leaderboard_page(
+            &LeaderboardKey::Tippers {
+                token_id: token_id.unwrap_or_else(near_token_id),
+            },
+            cursor,
+            limit,
+        )
+    }
+
+    fn leaderboard_page(
+        &self,
+        key: &LeaderboardKey,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Page<LeaderboardEntry> {
+        let board = self.leaderboards.get(key).map_or(&[][..], |board| {
+            &board[..board.len().min(self.leaderboard_size as usize)]
+        });
+        paginate(board.iter(), board.len() as u64, cursor, limit, |entry| {
+            Some(entry.clone())
+        })
+    }
+
+    // Internal helper to move an entrant to its new total, tipped less reclaimed. The entry
+    // bubbles up or down from its old position, or up from the end of the board
+    pub(crate) fn internal_update_leaderboard(
+        &mut self,
+        key: LeaderboardKey,
+        id: String,
+        total: u128,
+    ) {
+        let size = self.leaderboard_size as usize;
+        let board = self.leaderboards.entry(key).or_default();
+        board.truncate(size);
+
+        let mut position = match board.iter().position(|entry| entry.id == id) {
+            Some(position) if total == 0 => {
+                board.remove(position);
+                return;
+            }
+            Some(position) => position,
+            None if total == 0 => return,
+            None if board.len() < size => {
+                board.push(LeaderboardEntry {
+                    id,
+                    total: U128(total),
+                });
+                board.len() - 1
+            }
+            None if board.last().is_some_and(|last| last.total.0 < total) => {
+                let last = board.len() - 1;
+                board[last].id = id;
+                last
+            }
+            None => return,
+        };
+
+        board[position].total = U128(total);
+        while position > 0 && board[position - 1].total.0 < total {
+            board.swap(position - 1, position);
+            position -= 1;
+        }
+        while position + 1 < board.len() && board[position + 1].total.0 > total {
+            board.swap(position, position + 1);
+            position += 1;
+        }
+    }
+}
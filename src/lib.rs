@@ -1,21 +1,60 @@
-use near_sdk::json_types::U128;
-use near_sdk::store::{IterableMap, IterableSet};
+use near_sdk::json_types::{Base58CryptoHash, U128};
+use near_sdk::store::{IterableMap, IterableSet, LookupMap, LookupSet, TreeMap};
 use near_sdk::{
-    env, near, near_bindgen, require, serde_json, AccountId, BorshStorageKey, Gas, NearToken,
-    PanicOnDefault, Promise, PromiseError, PromiseOrValue,
+    bs58, env, near, near_bindgen, require, serde_json, AccountId, BorshStorageKey, CryptoHash,
+    Gas, NearToken, PanicOnDefault, Promise, PromiseError, PromiseOrValue, PromiseResult,
 };
 
+mod bounty;
+mod campaign;
 mod claim;
+mod dispute;
+mod escrow;
 mod events;
 mod external;
+mod leaderboard;
+mod link;
+mod migration;
+mod moderation;
+mod ownership;
+mod pagination;
+mod pause;
 mod proof;
+mod reclaim;
+mod roles;
+mod round;
+mod routing;
+mod settlement;
+mod stats;
+mod storage;
+mod sweep;
+mod timelock;
 mod token;
 // mod utils;
 
 use crate::events::*;
-use claim::{format_claim, Claim, ClaimExternal, ClaimType};
+use campaign::{Campaign, CampaignId};
+use claim::{
+    format_claim_v1, Claim, ClaimExternal, ClaimExternalV1, ClaimStatus, ClaimType, TipExpiry,
+    TipTerms,
+};
+use dispute::Dispute;
+use leaderboard::{LeaderboardEntry, LeaderboardKey, DEFAULT_LEADERBOARD_SIZE};
+use link::{HandleRename, LinkProof, LinkRecord};
+use migration::LegacyState;
+use moderation::BlockRecord;
+use ownership::PendingOwner;
+use pagination::{paginate, paginate_keys, Page};
+use pause::{Operation, ALL_OPERATIONS};
 use proof::ReclaimProof;
-use token::TokenInfo;
+use roles::Role;
+use round::{HandleMatch, Round, RoundId};
+use routing::TipRoute;
+use settlement::{public_beneficiary, Settlement, SettlementKind};
+use stats::{StatsKey, TipStats, TokenStats};
+use sweep::{SweepConfig, SweepDestination, MAX_SWEEP_INCENTIVE_BPS};
+use timelock::{ActionId, AdminAction, QueuedAction};
+use token::{near_token_id, ExpiryConfig, TokenInfo, TokenStandard};
 
 type ClaimId = u64;
 
@@ -23,26 +62,83 @@ type ClaimId = u64;
 const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
 /// Gas for NFT transfers
 const GAS_FOR_NFT_TRANSFER: Gas = Gas::from_tgas(10);
+/// Gas for donating a tip through a PotLock contract
+const GAS_FOR_DONATION: Gas = Gas::from_tgas(50);
 /// Gas for Reclaim Protocol verification
 const GAS_FOR_RECLAIM_VERIFY: Gas = Gas::from_tgas(27);
+/// Gas for the callback that settles a claim once its transfer resolves. It updates the
+/// escrow, stats, leaderboards and rounds, so it needs more than a plain status change
+const GAS_FOR_SETTLEMENT_CALLBACK: Gas = Gas::from_tgas(20);
+/// Gas for the `migrate` call that follows an upgrade, enough for one migration batch
+const GAS_FOR_MIGRATE: Gas = Gas::from_tgas(150);
 /// Maximum time allowed between proof generation and submission (5 minutes)
 const MAX_PROOF_AGE: u64 = 5 * 60 * 1_000_000_000;
 /// Claim expiration period (90 days)
 const CLAIM_EXPIRATION_PERIOD: u64 = 90 * 24 * 60 * 60 * 1_000_000_000;
+/// Default shortest expiry a tipper can choose (1 day)
+const MIN_CLAIM_EXPIRATION_PERIOD: u64 = 24 * 60 * 60 * 1_000_000_000;
+/// Default longest expiry a tipper can choose or extend to (365 days)
+const MAX_CLAIM_EXPIRATION_PERIOD: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+/// `expires_at` of claims that never expire
+const NEVER_EXPIRES: u64 = u64::MAX;
+/// Default time a link stays valid before it must be refreshed (180 days)
+const DEFAULT_LINK_VALIDITY_PERIOD: u64 = 180 * 24 * 60 * 60 * 1_000_000_000;
+/// Bond required to dispute a linked handle
+const DISPUTE_BOND: NearToken = NearToken::from_near(1);
+/// Time a dispute freezes the handle's payouts while an arbiter reviews it (7 days)
+const DISPUTE_REVIEW_PERIOD: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+/// Default delay before a queued admin action can be executed (2 days)
+const DEFAULT_ADMIN_ACTION_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+/// Default delay between proposing and accepting ownership (2 days)
+const DEFAULT_OWNERSHIP_TRANSFER_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+/// Maximum length of a tip memo
+const MAX_MEMO_LENGTH: usize = 256;
 /// Maximum claims to process in a single batch
 const MAX_CLAIMS_PER_BATCH: usize = 100;
 
-/// Gas for cross-contract calls
-const XCC_GAS_DEFAULT: u64 = 10;
-
 pub const TOKEN_REGISTRATION_FEE: NearToken = NearToken::from_near(1);
 
 #[near(serializers = [borsh])]
 #[derive(BorshStorageKey)]
 pub enum StorageKey {
-    LinkedAccounts,
+    // Legacy variants hold the original release collections, emptied by the migration
+    LegacyLinkedAccounts,
     HandleClaims,
     ClaimsByHandle { platform: String, handle: String },
+    LegacyClaimsById,
+    LegacySupportedTokens,
+    Campaigns,
+    CampaignClaims,
+    ClaimsByCampaign { campaign_id: CampaignId },
+    Rounds,
+    ActiveRounds,
+    RoundHandles,
+    HandlesByRound { round_id: RoundId },
+    RoundMatches,
+    RoundContributions,
+    TipRoutes,
+    RoleMembers,
+    RoleMembersByRole { role: Role },
+    QueuedActions,
+    PausedOperations,
+    EscrowTotals,
+    EscrowedNfts,
+    BlockedHandles,
+    BlockedAccounts,
+    Disputes,
+    HandlesByUserId,
+    RenamedHandles,
+    HandlesByAccount,
+    AccountHandles { account_id: AccountId },
+    ClaimsByTipper,
+    TipperClaims { tipper: AccountId },
+    TipStats,
+    TokenStats,
+    TippedPairs,
+    Leaderboards,
+    Settlements,
+    StorageBalances,
+    Links,
     ClaimsById,
     SupportedTokens,
 }
@@ -62,9 +158,11 @@ impl SocialHandle {
             handle: handle.to_lowercase(),
         }
     }
+}
 
-    pub fn to_string(&self) -> String {
-        format!("{}:{}", self.platform, self.handle)
+impl std::fmt::Display for SocialHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.platform, self.handle)
     }
 }
 
@@ -73,12 +171,22 @@ impl SocialHandle {
 pub struct Contract {
     /// Owner account ID
     pub owner_id: AccountId,
+    /// Owner proposed by `propose_owner`, waiting to accept
+    pub pending_owner: Option<PendingOwner>,
+    /// Minimum time between proposing and accepting a new owner, in nanoseconds
+    pub ownership_transfer_delay: u64,
 
     /// Reclaim Protocol contract ID for verification
     pub reclaim_contract_id: AccountId,
 
-    /// Mapping of social media handles to NEAR accounts
-    pub linked_accounts: IterableMap<String, AccountId>,
+    /// Links of social media handles to NEAR accounts
+    pub links: TreeMap<SocialHandle, LinkRecord>,
+    /// Handle each platform user is currently linked under, keyed by (platform, user id)
+    pub handles_by_user_id: LookupMap<(String, String), SocialHandle>,
+    /// Old handles of renamed platform users, mapped to their new handle
+    pub renamed_handles: LookupMap<SocialHandle, HandleRename>,
+    /// Handles linked to each account
+    pub handles_by_account: LookupMap<AccountId, TreeMap<SocialHandle, ()>>,
 
     pub next_claim_id: ClaimId,
 
@@ -87,11 +195,85 @@ pub struct Contract {
     /// Claims for unlinked accounts
     pub handle_claims: IterableMap<String, IterableSet<ClaimId>>,
 
-    /// Supported tokens (FTs and NFTs)
-    pub supported_tokens: IterableMap<AccountId, TokenInfo>,
+    /// Claims created by each tipper, anonymous ones included
+    pub claims_by_tipper: LookupMap<AccountId, IterableSet<ClaimId>>,
 
-    /// Contract paused state
-    pub paused: bool,
+    /// Tip aggregates per handle and per tipper
+    pub tip_stats: LookupMap<StatsKey, TipStats>,
+    /// Per-token totals of each `tip_stats` entry
+    pub token_stats: LookupMap<(StatsKey, AccountId), TokenStats>,
+    /// (handle, tipper) pairs seen, to count unique tippers
+    pub tipped_pairs: LookupSet<(StatsKey, String)>,
+    /// Top entries by tipped total, largest first
+    pub leaderboards: LookupMap<LeaderboardKey, Vec<LeaderboardEntry>>,
+    /// Number of entries kept on each leaderboard
+    pub leaderboard_size: u32,
+
+    /// Supported tokens (FTs and NFTs)
+    pub supported_tokens: TreeMap<AccountId, TokenInfo>,
+    /// Expiration rules for NEAR claims, which have no `supported_tokens` entry
+    pub near_expiry: ExpiryConfig,
+
+    pub next_campaign_id: CampaignId,
+    /// Funding campaigns run by linked handles
+    pub campaigns: IterableMap<CampaignId, Campaign>,
+    /// Contributions escrowed for each campaign, kept out of `handle_claims`
+    pub campaign_claims: LookupMap<CampaignId, IterableSet<ClaimId>>,
+
+    pub next_round_id: RoundId,
+    /// Quadratic funding rounds matching tips from a sponsor pool
+    pub rounds: IterableMap<RoundId, Round>,
+    /// Rounds not yet finalized, checked on every tip
+    pub active_rounds: IterableSet<RoundId>,
+    /// Handles that received contributions in each round
+    pub round_handles: LookupMap<RoundId, IterableSet<SocialHandle>>,
+    pub round_matches: LookupMap<(RoundId, SocialHandle), HandleMatch>,
+    /// Total contributed by each unique tipper to a handle within a round
+    pub round_contributions: LookupMap<(RoundId, SocialHandle, AccountId), u128>,
+
+    /// Handles whose tips are donated to PotLock instead of paid to the linked account
+    pub tip_routes: LookupMap<String, TipRoute>,
+
+    /// Accounts holding each admin role, in addition to the owner
+    pub role_members: LookupMap<Role, TreeMap<AccountId, ()>>,
+
+    pub next_action_id: ActionId,
+    /// Admin actions waiting out the timelock
+    pub queued_actions: TreeMap<ActionId, QueuedAction>,
+    /// Delay before a queued admin action can be executed, in nanoseconds
+    pub admin_action_delay: u64,
+
+    /// Operations currently paused
+    pub paused_operations: IterableSet<Operation>,
+
+    /// Amount of each token owed to pending claims and open round pools, `near` for NEAR
+    pub escrow_totals: LookupMap<AccountId, u128>,
+    /// NFTs held for pending claims, as (contract, token id)
+    pub escrowed_nfts: LookupSet<(AccountId, String)>,
+
+    /// Handles that can't be linked, tipped or claimed
+    pub blocked_handles: TreeMap<String, BlockRecord>,
+    /// Accounts that can't tip
+    pub blocked_accounts: TreeMap<AccountId, BlockRecord>,
+
+    /// Open link disputes by handle
+    pub disputes: TreeMap<String, Dispute>,
+    /// Time a link stays valid after verification, in nanoseconds
+    pub link_validity_period: u64,
+
+    /// How `sweep_expired` settles claims left unreclaimed
+    pub sweep_config: SweepConfig,
+    /// Position in `claims_by_id` where the next `sweep_expired` call resumes
+    pub sweep_cursor: u64,
+
+    /// Claim transfers in flight or waiting for a retry
+    pub settlements: TreeMap<ClaimId, Settlement>,
+
+    /// NEAR deposited by tippers to cover the storage of their tips
+    pub storage_balances: LookupMap<AccountId, u128>,
+
+    /// Original release state still to be moved by `migrate_batch`, None once migrated
+    pub legacy: Option<LegacyState>,
 }
 
 #[near_bindgen]
@@ -100,15 +282,57 @@ impl Contract {
     pub fn new(owner_id: AccountId, reclaim_contract_id: AccountId) -> Self {
         assert!(!env::state_exists(), "Already initialized");
 
+        Self::internal_new(owner_id, reclaim_contract_id)
+    }
+
+    // Internal helper building empty state, shared by `new` and `migrate`
+    fn internal_new(owner_id: AccountId, reclaim_contract_id: AccountId) -> Self {
         Self {
             owner_id,
+            pending_owner: None,
+            ownership_transfer_delay: DEFAULT_OWNERSHIP_TRANSFER_DELAY,
             reclaim_contract_id,
-            linked_accounts: IterableMap::new(StorageKey::LinkedAccounts),
+            links: TreeMap::new(StorageKey::Links),
+            handles_by_user_id: LookupMap::new(StorageKey::HandlesByUserId),
+            renamed_handles: LookupMap::new(StorageKey::RenamedHandles),
+            handles_by_account: LookupMap::new(StorageKey::HandlesByAccount),
             next_claim_id: 1,
             claims_by_id: IterableMap::new(StorageKey::ClaimsById),
             handle_claims: IterableMap::new(StorageKey::HandleClaims),
-            supported_tokens: IterableMap::new(StorageKey::SupportedTokens),
-            paused: false,
+            claims_by_tipper: LookupMap::new(StorageKey::ClaimsByTipper),
+            tip_stats: LookupMap::new(StorageKey::TipStats),
+            token_stats: LookupMap::new(StorageKey::TokenStats),
+            tipped_pairs: LookupSet::new(StorageKey::TippedPairs),
+            leaderboards: LookupMap::new(StorageKey::Leaderboards),
+            leaderboard_size: DEFAULT_LEADERBOARD_SIZE,
+            supported_tokens: TreeMap::new(StorageKey::SupportedTokens),
+            near_expiry: ExpiryConfig::default(),
+            next_campaign_id: 1,
+            campaigns: IterableMap::new(StorageKey::Campaigns),
+            campaign_claims: LookupMap::new(StorageKey::CampaignClaims),
+            next_round_id: 1,
+            rounds: IterableMap::new(StorageKey::Rounds),
+            active_rounds: IterableSet::new(StorageKey::ActiveRounds),
+            round_handles: LookupMap::new(StorageKey::RoundHandles),
+            round_matches: LookupMap::new(StorageKey::RoundMatches),
+            round_contributions: LookupMap::new(StorageKey::RoundContributions),
+            tip_routes: LookupMap::new(StorageKey::TipRoutes),
+            role_members: LookupMap::new(StorageKey::RoleMembers),
+            next_action_id: 0,
+            queued_actions: TreeMap::new(StorageKey::QueuedActions),
+            admin_action_delay: DEFAULT_ADMIN_ACTION_DELAY,
+            paused_operations: IterableSet::new(StorageKey::PausedOperations),
+            escrow_totals: LookupMap::new(StorageKey::EscrowTotals),
+            escrowed_nfts: LookupSet::new(StorageKey::EscrowedNfts),
+            blocked_handles: TreeMap::new(StorageKey::BlockedHandles),
+            blocked_accounts: TreeMap::new(StorageKey::BlockedAccounts),
+            disputes: TreeMap::new(StorageKey::Disputes),
+            link_validity_period: DEFAULT_LINK_VALIDITY_PERIOD,
+            sweep_config: SweepConfig::default(),
+            sweep_cursor: 0,
+            settlements: TreeMap::new(StorageKey::Settlements),
+            storage_balances: LookupMap::new(StorageKey::StorageBalances),
+            legacy: None,
         }
     }
 
@@ -120,40 +344,23 @@ impl Contract {
         handle: String,
         proof: ReclaimProof,
     ) -> Promise {
-        require!(!self.paused, "Contract is paused");
+        self.assert_not_paused(Operation::Link);
         require!(
             env::attached_deposit() >= NearToken::from_yoctonear(1),
             "Requires attached deposit"
         );
 
         let social_handle = SocialHandle::new(platform.clone(), handle.clone());
+        self.assert_handle_not_blocked(&social_handle);
+        // An expired link can be taken over with a fresh proof
+        require!(self.link_stale(&social_handle), "Handle already linked");
         require!(
-            !self
-                .linked_accounts
-                .contains_key(&social_handle.to_string()),
-            "Handle already linked"
+            !self.links.contains_key(&social_handle) || proof.is_recent(env::block_timestamp()),
+            "Proof is too old"
         );
 
-        match serde_json::from_str::<serde_json::Value>(&proof.claimInfo.context) {
-            Ok(context_json) => {
-                if let Some(extracted_param) = context_json.get("extractedParameters") {
-                    if let Some(screen_name_value) = extracted_param.get("screen_name") {
-                        if let Some(screen_name) = screen_name_value.as_str() {
-                            env::log_str(&format!("screen_name: {}, {}", screen_name, handle));
-                            assert_eq!(
-                                screen_name, handle,
-                                "Proven handle does not match passed handle"
-                            );
-                        }
-                    } else {
-                        env::panic_str("screen_name not found in extractedParameters");
-                    }
-                } else {
-                    env::panic_str("needed params not found");
-                }
-            }
-            Err(_) => env::panic_str("Failed to parse context Json for verification"),
-        }
+        proof.assert_handle(&handle);
+        let link_proof = LinkProof::from_proof(&proof);
 
         // Verify proof through Reclaim Protocol
         external::ext_reclaim::ext(self.reclaim_contract_id.clone())
@@ -162,7 +369,11 @@ impl Contract {
             .then(
                 Self::ext(env::current_account_id())
                     .with_static_gas(Gas::from_tgas(5))
-                    .on_link_account_verified(social_handle, env::predecessor_account_id()),
+                    .on_link_account_verified(
+                        social_handle,
+                        env::predecessor_account_id(),
+                        link_proof,
+                    ),
             )
     }
 
@@ -171,30 +382,129 @@ impl Contract {
         &mut self,
         social_handle: SocialHandle,
         account_id: AccountId,
+        link_proof: LinkProof,
         #[callback_result] verification_result: Result<(), near_sdk::PromiseError>,
     ) {
         if verification_result.is_err() {
             env::panic_str("Proof verification failed")
         } else {
-            self.linked_accounts
-                .insert(social_handle.to_string(), account_id.clone());
+            // The handle may have been linked or refreshed while the proof was being verified
+            require!(
+                self.link_stale(&social_handle)
+                    || self.linked_account(&social_handle) == Some(&account_id),
+                "Handle already linked"
+            );
+            self.internal_link(&social_handle, account_id.clone(), link_proof);
             log_account_linked_event(&social_handle.platform, &social_handle.handle, &account_id);
         }
     }
 
-    /// Tip with native NEAR
+    /// Tip with native NEAR. Anonymous tips hide the tipper in views and events only: the
+    /// tipper signs the transaction and is kept in contract state, so it stays public on chain.
+    /// With an `arbiter` the tip is held as a bounty until the arbiter approves it,
+    /// with a `campaign_id` it is escrowed as a contribution to that campaign.
+    /// `terms` carry the tipper's memo and an expiry within the token's bounds
     #[payable]
-    pub fn tip_near(&mut self, platform: String, handle: String) -> PromiseOrValue<()> {
-        require!(!self.paused, "Contract is paused");
+    pub fn tip_near(
+        &mut self,
+        platform: String,
+        handle: String,
+        anonymous: Option<bool>,
+        arbiter: Option<AccountId>,
+        campaign_id: Option<CampaignId>,
+        terms: Option<TipTerms>,
+    ) -> PromiseOrValue<()> {
+        self.assert_not_paused(Operation::TipNear);
         let amount = env::attached_deposit();
         require!(
             amount > NearToken::from_yoctonear(0),
             "Requires attached deposit"
         );
 
+        require!(
+            arbiter.is_none() || campaign_id.is_none(),
+            "A tip can't be both a bounty and a campaign contribution"
+        );
+
         let social_handle = SocialHandle::new(platform, handle);
+        self.assert_handle_not_blocked(&social_handle);
+        self.assert_account_not_blocked(&env::predecessor_account_id());
+        let terms = terms.unwrap_or_default();
+        let expires_at = self.internal_expires_at(&near_token_id(), terms.expiry);
+        require!(
+            !terms.cancellable || campaign_id.is_none(),
+            "Campaign contributions can't be cancellable"
+        );
+        // Tips held as pending claims pay for their storage, out of the deposit if need be
+        let pending = arbiter.is_some()
+            || campaign_id.is_some()
+            || self.payout_account(&social_handle).is_none();
+        let amount = if pending {
+            NearToken::from_yoctonear(self.internal_charge_tip_storage(
+                &env::predecessor_account_id(),
+                Some(amount.as_yoctonear()),
+            ))
+        } else {
+            amount
+        };
+
+        self.internal_record_tip_stats(
+            &social_handle,
+            &env::predecessor_account_id(),
+            &near_token_id(),
+            amount.as_yoctonear(),
+            anonymous.unwrap_or(false),
+        );
+        // Counted towards the rounds active now, bounties and campaign contributions aside
+        let rounds = if arbiter.is_none() && campaign_id.is_none() {
+            self.internal_record_round_contribution(
+                &social_handle,
+                &env::predecessor_account_id(),
+                &near_token_id(),
+                amount.as_yoctonear(),
+            )
+        } else {
+            vec![]
+        };
+
+        if let Some(recipient) = self
+            .payout_account(&social_handle)
+            .filter(|_| arbiter.is_none() && campaign_id.is_none())
+            .cloned()
+        {
+            // Routed tips are donated on the creator's behalf, falling back to a pending claim
+            let mut claim = Claim::new_near(
+                env::predecessor_account_id(),
+                amount.as_yoctonear(),
+                social_handle.to_string(),
+            );
+            if anonymous.unwrap_or(false) {
+                claim.set_anonymous();
+            }
+            claim.set_memo(terms.memo.clone());
+            claim.expires_at = expires_at;
+            claim.cancellable = terms.cancellable;
+            claim.rounds = rounds;
+            let message = format!("Tip from {}", claim.public_tipper());
+            if let Some(forward) =
+                self.internal_forward(&social_handle, &claim, &recipient, message)
+            {
+                log_tip_forwarded_event(
+                    &social_handle.platform,
+                    &social_handle.handle,
+                    amount.as_yoctonear().into(),
+                    "NEAR",
+                    &recipient,
+                );
+                return PromiseOrValue::Promise(
+                    forward.then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(Gas::from_tgas(10))
+                            .on_tip_forwarded(social_handle, claim),
+                    ),
+                );
+            }
 
-        if let Some(recipient) = self.linked_accounts.get(&social_handle.to_string()) {
             // Direct transfer for linked accounts
             log_tip_transferred_event(
                 &social_handle.platform,
@@ -204,14 +514,27 @@ impl Contract {
                 &recipient,
             );
 
-            PromiseOrValue::Promise(Promise::new(recipient.clone()).transfer(amount))
+            PromiseOrValue::Promise(Promise::new(recipient).transfer(amount))
         } else {
             // Store as pending claim
-            let claim = Claim::new_near(
+            let mut claim = Claim::new_near(
                 env::predecessor_account_id(),
                 amount.as_yoctonear(),
                 social_handle.to_string(),
             );
+            if anonymous.unwrap_or(false) {
+                claim.set_anonymous();
+            }
+            claim.set_memo(terms.memo.clone());
+            claim.expires_at = expires_at;
+            claim.cancellable = terms.cancellable;
+            claim.rounds = rounds;
+            if let Some(arbiter) = arbiter {
+                claim.set_arbiter(arbiter);
+            }
+            if let Some(campaign_id) = campaign_id {
+                self.internal_add_to_campaign(campaign_id, &mut claim);
+            }
 
             self.store_claim(social_handle, claim);
             PromiseOrValue::Value(())
@@ -219,21 +542,39 @@ impl Contract {
     }
 
     // Internal helper to store claims
-    fn store_claim(&mut self, social_handle: SocialHandle, claim: Claim) {
+    fn store_claim(&mut self, social_handle: SocialHandle, claim: Claim) -> ClaimId {
         let claim_id = self.next_claim_id;
         self.next_claim_id += 1;
+        self.internal_escrow_claim(&claim);
         self.claims_by_id.insert(claim_id, claim.clone());
-        let storage_key = StorageKey::ClaimsByHandle {
-            platform: social_handle.platform.clone(),
-            handle: social_handle.handle.clone(),
-        };
-
-        let empty_handle_claim: IterableSet<ClaimId> = IterableSet::new(storage_key);
+        self.claims_by_tipper
+            .entry(claim.tipper().clone())
+            .or_insert_with(|| {
+                IterableSet::new(StorageKey::TipperClaims {
+                    tipper: claim.tipper().clone(),
+                })
+            })
+            .insert(claim_id);
 
-        let claim_ids = self
-            .handle_claims
-            .entry(social_handle.to_string())
-            .or_insert(empty_handle_claim);
+        // Campaign contributions are only claimable through the campaign
+        let claim_ids = if let Some(campaign_id) = claim.campaign_id {
+            self.campaign_claims
+                .entry(campaign_id)
+                .or_insert_with(|| IterableSet::new(StorageKey::ClaimsByCampaign { campaign_id }))
+        } else {
+            let storage_key = StorageKey::ClaimsByHandle {
+                platform: social_handle.platform.clone(),
+                handle: social_handle.handle.clone(),
+            };
+
+            let empty_handle_claim: IterableSet<ClaimId> = IterableSet::new(storage_key);
+            // New tips are for whoever holds the handle now, not a user that renamed away
+            self.renamed_handles.remove(&social_handle);
+
+            self.handle_claims
+                .entry(social_handle.to_string())
+                .or_insert(empty_handle_claim)
+        };
 
         claim_ids.insert(claim_id);
 
@@ -242,27 +583,48 @@ impl Contract {
             &social_handle.handle,
             claim.amount().into(),
             claim.token_type(),
-            claim.tipper(),
+            &claim.public_tipper(),
         );
+        if let Some(arbiter) = &claim.arbiter {
+            log_bounty_created_event(
+                claim_id,
+                &social_handle.platform,
+                &social_handle.handle,
+                arbiter,
+            );
+        }
+
+        claim_id
     }
 
     #[payable]
     pub fn claim(&mut self, platform: String, handle: String) {
-        require!(!self.paused, "Contract is paused");
+        self.assert_not_paused(Operation::Claim);
 
         let social_handle = SocialHandle::new(platform.clone(), handle.clone());
+        self.assert_handle_not_blocked(&social_handle);
+        // Claims made before a rename are collected through the handle's new link
+        let (payout_handle, claims_before) = self.resolve_handle(&social_handle);
+        require!(
+            !self.payouts_frozen(&payout_handle),
+            "Payouts for this handle are frozen by a dispute"
+        );
+        require!(
+            !self.link_stale(&payout_handle),
+            "Link has expired, refresh it with refresh_link"
+        );
 
         let account_id = self
-            .linked_accounts
-            .get(&social_handle.to_string())
+            .linked_account(&payout_handle)
+            .cloned()
             .unwrap_or_else(|| env::panic_str("Account must be linked before claiming."));
         require!(
-            env::predecessor_account_id().eq(account_id),
+            env::predecessor_account_id().eq(&account_id),
             "Only the linked account can claim tips"
         );
 
         // Process claims if any exist
-        if let Some(claims_ids) = self.handle_claims.get_mut(&social_handle.to_string()) {
+        if let Some(claims_ids) = self.handle_claims.get(&social_handle.to_string()) {
             if claims_ids.is_empty() {
                 env::panic_str("No pending claims to process.");
             }
@@ -275,79 +637,26 @@ impl Contract {
                 claims_ids.len()
             ));
 
-            for claim_id in claims_ids.iter().take(MAX_CLAIMS_PER_BATCH).cloned() {
-                if let Some(claim) = self.claims_by_id.get(&claim_id) {
-                    if claim.is_expired() {
-                        // Track expired claims for removal
-                        // claims_vector.remove(claim);
+            let claim_ids: Vec<ClaimId> = claims_ids
+                .iter()
+                .filter(|claim_id| claims_before.is_none_or(|before| **claim_id < before))
+                .take(MAX_CLAIMS_PER_BATCH)
+                .cloned()
+                .collect();
+            for claim_id in claim_ids {
+                if let Some(claim) = self.claims_by_id.get(&claim_id).cloned() {
+                    // Skips expired, settled and unapproved claims
+                    if !claim.is_claimable() {
                         continue;
                     }
-                    match &claim.claim_type {
-                        ClaimType::Near => {
-                            // NEAR transfer
-                            Promise::new(account_id.clone())
-                                .transfer(claim.amount)
-                                .then(
-                                    Self::ext(env::current_account_id())
-                                        .with_static_gas(Gas::from_tgas(5))
-                                        .on_transfer_complete(
-                                            social_handle.clone(),
-                                            "NEAR".to_string(),
-                                            claim_id,
-                                            account_id.clone(),
-                                            None,
-                                        ),
-                                )
-                        }
-                        ClaimType::FungibleToken { contract_id } => {
-                            // FT transfer with callback
-                            external::ext_ft::ext(contract_id.clone())
-                                .with_attached_deposit(NearToken::from_yoctonear(1))
-                                .with_static_gas(Gas::from_tgas(XCC_GAS_DEFAULT))
-                                .ft_transfer(
-                                    account_id.clone(),
-                                    claim.amount.to_string(),
-                                    Some(format!("Claimed tip from {}", claim.tipper)),
-                                )
-                                .then(
-                                    Self::ext(env::current_account_id())
-                                        .with_static_gas(Gas::from_tgas(5))
-                                        .on_transfer_complete(
-                                            social_handle.clone(),
-                                            "FT".to_string(),
-                                            claim_id,
-                                            account_id.clone(),
-                                            None,
-                                        ),
-                                )
-                        }
-                        ClaimType::NonFungibleToken {
-                            contract_id,
-                            token_id,
-                        } => {
-                            // NFT transfer with callback
-                            external::ext_nft::ext(contract_id.clone())
-                                .with_attached_deposit(NearToken::from_yoctonear(1))
-                                .with_static_gas(Gas::from_tgas(XCC_GAS_DEFAULT))
-                                .nft_transfer(
-                                    account_id.clone(),
-                                    token_id.clone(),
-                                    None,
-                                    Some(format!("Claimed tip from {}", claim.tipper)),
-                                )
-                                .then(
-                                    Self::ext(env::current_account_id())
-                                        .with_static_gas(Gas::from_tgas(5))
-                                        .on_transfer_complete(
-                                            social_handle.clone(),
-                                            "NFT".to_string(),
-                                            claim_id,
-                                            account_id.clone(),
-                                            None,
-                                        ),
-                                )
-                        }
-                    };
+                    let memo = format!("Claimed tip from {}", claim.public_tipper());
+                    self.internal_payout_claim(
+                        social_handle.clone(),
+                        claim_id,
+                        claim,
+                        account_id.clone(),
+                        memo,
+                    );
                 }
             }
         } else {
@@ -355,6 +664,93 @@ impl Contract {
         }
     }
 
+    // Linked account that direct tips can be paid to, None while payouts are frozen
+    // or the link is stale
+    fn payout_account(&self, social_handle: &SocialHandle) -> Option<&AccountId> {
+        self.linked_account(social_handle)
+            .filter(|_| !self.payouts_frozen(social_handle) && !self.link_stale(social_handle))
+    }
+
+    // Internal helper to pay a claim to the linked account, or donate it if the handle is routed
+    fn internal_payout_claim(
+        &mut self,
+        social_handle: SocialHandle,
+        claim_id: ClaimId,
+        claim: Claim,
+        account_id: AccountId,
+        memo: String,
+    ) -> Promise {
+        if let Some(forward) =
+            self.internal_forward(&social_handle, &claim, &account_id, memo.clone())
+        {
+            // Locked like a transfer so the claim can't be forwarded twice while in flight
+            self.internal_lock_claim(
+                claim_id,
+                &claim,
+                account_id.clone(),
+                SettlementKind::Forward,
+            );
+            return self.internal_settle_forward(forward, social_handle, claim_id, account_id);
+        }
+        self.internal_transfer_claim(
+            social_handle,
+            claim_id,
+            claim,
+            account_id,
+            memo,
+            SettlementKind::Claim,
+        )
+    }
+
+    // Internal helper to pay out a claim's asset, settled in `on_transfer_complete`.
+    // The claim stays `Settling` until the transfer succeeds or its retries run out
+    pub(crate) fn internal_transfer_claim(
+        &mut self,
+        social_handle: SocialHandle,
+        claim_id: ClaimId,
+        claim: Claim,
+        receiver_id: AccountId,
+        memo: String,
+        kind: SettlementKind,
+    ) -> Promise {
+        let reclaim_trf = (kind != SettlementKind::Claim).then_some(true);
+        self.internal_lock_claim(claim_id, &claim, receiver_id.clone(), kind);
+        self.internal_send_settlement(
+            social_handle,
+            claim_id,
+            &claim,
+            receiver_id,
+            memo,
+            reclaim_trf,
+        )
+    }
+
+    pub(crate) fn internal_send_settlement(
+        &self,
+        social_handle: SocialHandle,
+        claim_id: ClaimId,
+        claim: &Claim,
+        receiver_id: AccountId,
+        memo: String,
+        reclaim_trf: Option<bool>,
+    ) -> Promise {
+        let transfer = claim
+            .claim_type
+            .transfer(receiver_id.clone(), claim.amount, memo);
+
+        transfer.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_SETTLEMENT_CALLBACK)
+                .on_transfer_complete(
+                    social_handle,
+                    claim.token_type().to_string(),
+                    claim_id,
+                    receiver_id,
+                    reclaim_trf,
+                ),
+        )
+    }
+
     #[private]
     pub fn on_transfer_complete(
         &mut self,
@@ -366,6 +762,10 @@ impl Contract {
         #[callback_result] transfer_result: Result<(), PromiseError>,
     ) {
         if transfer_result.is_err() {
+            self.internal_settlement_failed(
+                claim_id,
+                format!("{} transfer to {} failed", token_type, recipient),
+            );
             env::log_str(&format!(
                 "Transfer failed for {} {} for {:?}:{:?}",
                 if reclaim_trf.is_some() {
@@ -377,18 +777,38 @@ impl Contract {
                 social_handle.platform,
                 social_handle.handle
             ));
-        } else if let Some(claim) = self.claims_by_id.get_mut(&claim_id) {
-            // TODO: maybe merge this two events into one? since they emit same params?
+        } else if let Some(mut claim) = self.claims_by_id.get(&claim_id).cloned() {
+            let kind = self
+                .settlements
+                .remove(&claim_id)
+                .map(|settlement| settlement.kind);
+            self.internal_release_claim(&claim, claim.amount());
+            self.internal_record_settlement_stats(&claim, claim.amount(), reclaim_trf.is_some());
             if reclaim_trf.is_some() {
+                self.internal_reverse_round_contribution(&claim);
+            }
+            // TODO: maybe merge this two events into one? since they emit same params?
+            if kind == Some(SettlementKind::Cancel) {
+                claim.status = ClaimStatus::Reclaimed;
+                log_tip_cancelled_event(
+                    &social_handle.platform,
+                    &social_handle.handle,
+                    claim_id,
+                    claim.amount().into(),
+                    claim.token_type(),
+                    &claim.public_tipper(),
+                );
+            } else if reclaim_trf.is_some() {
+                claim.status = ClaimStatus::Reclaimed;
                 log_tip_reclaimed_event(
                     &social_handle.platform,
                     &social_handle.handle,
                     claim.amount().into(),
                     claim.token_type(),
-                    claim.tipper(),
+                    &claim.public_tipper(),
                 );
             } else {
-                claim.claimed = true;
+                claim.status = ClaimStatus::Claimed;
                 log_claim_processed_event(
                     &social_handle.platform,
                     &social_handle.handle,
@@ -397,98 +817,48 @@ impl Contract {
                     &recipient,
                 );
             }
+            self.claims_by_id.insert(claim_id, claim);
         }
     }
 
     pub fn reclaim_tip(&mut self, platform: String, handle: String, claim_id: ClaimId) -> Promise {
-        require!(!self.paused, "Contract is paused");
+        self.assert_not_paused(Operation::Reclaim);
 
         let social_handle = SocialHandle::new(platform, handle);
 
         // Get the claims for this handle
-        if let Some(claim) = self.claims_by_id.get_mut(&claim_id) {
-            assert!(!claim.claimed, "tip has been claimed");
-            assert!(claim.is_expired(), "claim is not yet expired");
+        if let Some(claim) = self.claims_by_id.get(&claim_id).cloned() {
+            assert!(
+                !matches!(claim.status, ClaimStatus::Claimed | ClaimStatus::Reclaimed),
+                "tip has been claimed"
+            );
+            require!(
+                claim.status != ClaimStatus::Settling,
+                "Claim is being settled, see retry_settlement"
+            );
+            assert!(claim.is_reclaimable(), "claim is not yet expired");
 
             // Verify the caller is the original tipper
             require!(
                 &env::predecessor_account_id() == claim.tipper(),
                 "Only the original tipper can reclaim funds"
             );
-
-            match &claim.claim_type {
-                ClaimType::Near => Promise::new(env::predecessor_account_id())
-                    .transfer(claim.amount)
-                    .then(
-                        Self::ext(env::current_account_id())
-                            .with_static_gas(Gas::from_tgas(5))
-                            .on_transfer_complete(
-                                social_handle.clone(),
-                                "NEAR".to_string(),
-                                claim_id,
-                                env::predecessor_account_id(),
-                                Some(true),
-                            ),
-                    ),
-                ClaimType::FungibleToken { contract_id } => {
-                    external::ext_ft::ext(contract_id.clone())
-                        .with_attached_deposit(NearToken::from_yoctonear(1))
-                        .with_static_gas(GAS_FOR_FT_TRANSFER)
-                        .ft_transfer(
-                            env::predecessor_account_id(),
-                            claim.amount.to_string(),
-                            Some("Reclaimed expired tip".to_string()),
-                        )
-                        .then(
-                            Self::ext(env::current_account_id())
-                                .with_static_gas(Gas::from_tgas(5))
-                                .on_transfer_complete(
-                                    social_handle.clone(),
-                                    "FT".to_string(),
-                                    claim_id,
-                                    env::predecessor_account_id(),
-                                    Some(true),
-                                ),
-                        )
-                }
-                ClaimType::NonFungibleToken {
-                    contract_id,
-                    token_id,
-                } => external::ext_nft::ext(contract_id.clone())
-                    .with_attached_deposit(NearToken::from_yoctonear(1))
-                    .with_static_gas(GAS_FOR_NFT_TRANSFER)
-                    .nft_transfer(
-                        env::predecessor_account_id(),
-                        token_id.clone(),
-                        None,
-                        Some("Reclaimed expired tip".to_string()),
-                    )
-                    .then(
-                        Self::ext(env::current_account_id())
-                            .with_static_gas(Gas::from_tgas(5))
-                            .on_transfer_complete(
-                                social_handle.clone(),
-                                "NFT".to_string(),
-                                claim_id,
-                                env::predecessor_account_id(),
-                                Some(true),
-                            ),
-                    ),
-            }
+            self.internal_transfer_claim(
+                social_handle,
+                claim_id,
+                claim,
+                env::predecessor_account_id(),
+                "Reclaimed expired tip".to_string(),
+                SettlementKind::Reclaim,
+            )
         } else {
             env::panic_str("No claims found for this handle");
         }
     }
 
-    pub fn set_reclaim_contract(&mut self, contract_id: AccountId) {
-        require!(
-            env::predecessor_account_id() == self.owner_id,
-            "Only owner can change Reclaim contract"
-        );
-
-        self.reclaim_contract_id = contract_id.clone();
-
-        env::log_str(&format!("Reclaim contract changed to {}", contract_id));
+    /// Queue a change of the Reclaim contract, applied after the timelock
+    pub fn set_reclaim_contract(&mut self, contract_id: AccountId) -> ActionId {
+        self.queue_admin_action(AdminAction::SetReclaimContract { contract_id })
     }
 
     /// Get the current Reclaim Protocol contract address
@@ -496,56 +866,19 @@ impl Contract {
         self.reclaim_contract_id.clone()
     }
 
-    pub fn pause(&mut self) {
-        // Only owner can pause
-        require!(
-            env::predecessor_account_id() == self.owner_id,
-            "Only owner can pause the contract"
-        );
-
-        require!(!self.paused, "Contract is already paused");
-        self.paused = true;
-
-        env::log_str("Contract paused by owner");
-    }
-
-    /// Unpause the contract (owner only)
-    pub fn unpause(&mut self) {
-        // Only owner can unpause
-        require!(
-            env::predecessor_account_id() == self.owner_id,
-            "Only owner can unpause the contract"
-        );
-
-        require!(self.paused, "Contract is not paused");
-        self.paused = false;
-
-        env::log_str("Contract unpaused by owner");
-    }
-
-    /// Check if contract is paused
-    pub fn is_paused(&self) -> bool {
-        self.paused
-    }
-
     pub fn register_token(&mut self, token_id: AccountId, token_info: TokenInfo) {
-        // Only owner can register tokens
+        self.assert_role(Role::TokenManager);
         require!(
-            env::predecessor_account_id() == self.owner_id,
-            "Only owner can register tokens"
+            token_id != near_token_id(),
+            "NEAR is always supported and can't be registered"
         );
 
         self.supported_tokens.insert(token_id, token_info);
     }
 
-    pub fn remove_token(&mut self, token_id: AccountId) {
-        // Only owner can remove tokens
-        require!(
-            env::predecessor_account_id() == self.owner_id,
-            "Only owner can remove tokens"
-        );
-
-        self.supported_tokens.remove(&token_id);
+    /// Queue the removal of a supported token, applied after the timelock
+    pub fn remove_token(&mut self, token_id: AccountId) -> ActionId {
+        self.queue_admin_action(AdminAction::RemoveToken { token_id })
     }
 
     #[payable]
@@ -555,12 +888,27 @@ impl Contract {
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128> {
-        require!(!self.paused, "Contract is paused");
+        self.assert_not_paused(Operation::FtTip);
+        self.assert_token_not_paused(&env::predecessor_account_id());
 
         // Parse the message to get platform and handle
         let parsed_msg: serde_json::Value =
             serde_json::from_str(&msg).unwrap_or_else(|_| env::panic_str("Invalid message format"));
 
+        // Matching pool top-up for a round in this token
+        if let Some(round_id) = parsed_msg["fund_round"].as_u64() {
+            let round = self
+                .rounds
+                .get(&round_id)
+                .unwrap_or_else(|| env::panic_str("Round not found"));
+            require!(
+                round.token_id == env::predecessor_account_id(),
+                "Round is not funded in this token"
+            );
+            self.internal_fund_round(round_id, &sender_id, amount.0);
+            return PromiseOrValue::Value(U128(0));
+        }
+
         let platform = parsed_msg["platform"]
             .as_str()
             .unwrap_or_else(|| env::panic_str("Missing platform field"))
@@ -571,7 +919,31 @@ impl Contract {
             .unwrap_or_else(|| env::panic_str("Missing handle field"))
             .to_string();
 
+        let anonymous = parsed_msg["anonymous"].as_bool().unwrap_or(false);
+        let memo = parsed_msg["memo"].as_str().map(|memo| memo.to_string());
+        let expiry: Option<TipExpiry> = parsed_msg.get("expiry").map(|expiry| {
+            serde_json::from_value(expiry.clone())
+                .unwrap_or_else(|_| env::panic_str("Invalid expiry"))
+        });
+        let arbiter: Option<AccountId> = parsed_msg["arbiter"].as_str().map(|arbiter| {
+            arbiter
+                .parse()
+                .unwrap_or_else(|_| env::panic_str("Invalid arbiter account"))
+        });
+        let campaign_id: Option<CampaignId> = parsed_msg["campaign_id"].as_u64();
+        require!(
+            arbiter.is_none() || campaign_id.is_none(),
+            "A tip can't be both a bounty and a campaign contribution"
+        );
+        let cancellable = parsed_msg["cancellable"].as_bool().unwrap_or(false);
+        require!(
+            !cancellable || campaign_id.is_none(),
+            "Campaign contributions can't be cancellable"
+        );
+
         let social_handle = SocialHandle::new(platform, handle);
+        self.assert_handle_not_blocked(&social_handle);
+        self.assert_account_not_blocked(&sender_id);
         let ft_contract_id = env::predecessor_account_id();
 
         // Verify token is supported
@@ -579,36 +951,102 @@ impl Contract {
             self.supported_tokens.contains_key(&ft_contract_id),
             "Unsupported token"
         );
+        let expires_at = self.internal_expires_at(&ft_contract_id, expiry);
 
         let amount_u128 = amount.0;
 
-        // If the handle is linked, forward the FT to the linked account
-        if let Some(recipient) = self.linked_accounts.get(&social_handle.to_string()) {
+        self.internal_record_tip_stats(
+            &social_handle,
+            &sender_id,
+            &ft_contract_id,
+            amount_u128,
+            anonymous,
+        );
+        let rounds = if arbiter.is_none() && campaign_id.is_none() {
+            self.internal_record_round_contribution(
+                &social_handle,
+                &sender_id,
+                &ft_contract_id,
+                amount_u128,
+            )
+        } else {
+            vec![]
+        };
+
+        // If the handle is linked, forward the FT to the linked account unless it's escrowed
+        if let Some(recipient) = self
+            .payout_account(&social_handle)
+            .filter(|_| arbiter.is_none() && campaign_id.is_none())
+            .cloned()
+        {
+            let mut claim = Claim::new_ft(
+                sender_id.clone(),
+                ft_contract_id.clone(),
+                amount_u128,
+                social_handle.to_string(),
+            );
+            if anonymous {
+                claim.set_anonymous();
+            }
+            claim.set_memo(memo.clone());
+            claim.expires_at = expires_at;
+            claim.cancellable = cancellable;
+            claim.rounds = rounds;
+            let message = format!("Tip from {}", claim.public_tipper());
+            if let Some(forward) =
+                self.internal_forward(&social_handle, &claim, &recipient, message.clone())
+            {
+                // Donate on the creator's behalf, falling back to a pending claim
+                log_tip_forwarded_event(
+                    &social_handle.platform,
+                    &social_handle.handle,
+                    amount_u128.into(),
+                    "FT",
+                    &recipient,
+                );
+                forward.then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(Gas::from_tgas(10))
+                        .on_tip_forwarded(social_handle, claim),
+                );
+                return PromiseOrValue::Value(U128(0));
+            }
+
             // Forward the tokens to the recipient
             external::ext_ft::ext(ft_contract_id.clone())
                 .with_attached_deposit(NearToken::from_yoctonear(1))
                 .with_static_gas(GAS_FOR_FT_TRANSFER)
-                .ft_transfer(
-                    recipient.clone(),
-                    amount.0.to_string(),
-                    Some(format!("Tip from {}", sender_id)),
-                );
+                .ft_transfer(recipient.clone(), amount.0.to_string(), Some(message));
 
             log_tip_transferred_event(
                 &social_handle.platform,
                 &social_handle.handle,
                 amount_u128.into(),
                 "FT",
-                recipient,
+                &recipient,
             );
         } else {
-            // Store as a claim for later
-            let claim = Claim::new_ft(
+            // Store as a claim for later. Panicking returns the tokens to the sender
+            self.internal_charge_tip_storage(&sender_id, None);
+            let mut claim = Claim::new_ft(
                 sender_id,
                 ft_contract_id,
                 amount_u128,
                 social_handle.to_string(),
             );
+            if anonymous {
+                claim.set_anonymous();
+            }
+            claim.set_memo(memo.clone());
+            claim.expires_at = expires_at;
+            claim.cancellable = cancellable;
+            claim.rounds = rounds;
+            if let Some(arbiter) = arbiter {
+                claim.set_arbiter(arbiter);
+            }
+            if let Some(campaign_id) = campaign_id {
+                self.internal_add_to_campaign(campaign_id, &mut claim);
+            }
             self.store_claim(social_handle, claim);
         }
 
@@ -624,7 +1062,8 @@ impl Contract {
         token_id: String,
         msg: String,
     ) -> PromiseOrValue<bool> {
-        require!(!self.paused, "Contract is paused");
+        self.assert_not_paused(Operation::NftTip);
+        self.assert_token_not_paused(&env::predecessor_account_id());
 
         // Parse the message to get platform and handle
         let parsed_msg: serde_json::Value =
@@ -640,7 +1079,22 @@ impl Contract {
             .unwrap_or_else(|| env::panic_str("Missing handle field"))
             .to_string();
 
+        let anonymous = parsed_msg["anonymous"].as_bool().unwrap_or(false);
+        let memo = parsed_msg["memo"].as_str().map(|memo| memo.to_string());
+        let expiry: Option<TipExpiry> = parsed_msg.get("expiry").map(|expiry| {
+            serde_json::from_value(expiry.clone())
+                .unwrap_or_else(|_| env::panic_str("Invalid expiry"))
+        });
+        let cancellable = parsed_msg["cancellable"].as_bool().unwrap_or(false);
+        // Panicking returns the NFT to the sender instead of tipping it unconditionally
+        require!(
+            parsed_msg.get("arbiter").is_none() && parsed_msg.get("campaign_id").is_none(),
+            "NFT tips can't be bounties or campaign contributions"
+        );
+
         let social_handle = SocialHandle::new(platform, handle);
+        self.assert_handle_not_blocked(&social_handle);
+        self.assert_account_not_blocked(&sender_id);
         let nft_contract_id = env::predecessor_account_id();
 
         // Verify token is supported
@@ -648,9 +1102,24 @@ impl Contract {
             self.supported_tokens.contains_key(&nft_contract_id),
             "Unsupported token"
         );
+        let expires_at = self.internal_expires_at(&nft_contract_id, expiry);
+        self.internal_record_tip_stats(&social_handle, &sender_id, &nft_contract_id, 1, anonymous);
+
+        let mut claim = Claim::new_nft(
+            sender_id,
+            nft_contract_id.clone(),
+            token_id.clone(),
+            social_handle.to_string(),
+        );
+        if anonymous {
+            claim.set_anonymous();
+        }
+        claim.set_memo(memo.clone());
+        claim.expires_at = expires_at;
+        claim.cancellable = cancellable;
 
         // If the handle is linked, forward the NFT to the linked account
-        if let Some(recipient) = self.linked_accounts.get(&social_handle.to_string()) {
+        if let Some(recipient) = self.payout_account(&social_handle) {
             // Forward the NFT to the recipient
             external::ext_nft::ext(nft_contract_id.clone())
                 .with_attached_deposit(NearToken::from_yoctonear(1))
@@ -659,7 +1128,7 @@ impl Contract {
                     recipient.clone(),
                     token_id.clone(),
                     None,
-                    Some(format!("Tip from {}", sender_id)),
+                    Some(format!("Tip from {}", claim.public_tipper())),
                 );
 
             log_tip_transferred_event(
@@ -671,12 +1140,7 @@ impl Contract {
             );
         } else {
             // Store as a claim for later
-            let claim = Claim::new_nft(
-                sender_id,
-                nft_contract_id,
-                token_id,
-                social_handle.to_string(),
-            );
+            self.internal_charge_tip_storage(claim.tipper(), None);
             self.store_claim(social_handle, claim);
         }
 
@@ -688,39 +1152,34 @@ impl Contract {
         self.supported_tokens.get(&token_id).cloned()
     }
 
-    pub fn get_supported_tokens(&self, from_index: u64, limit: u64) -> Vec<(AccountId, TokenInfo)> {
-        let keys = self.supported_tokens.keys().cloned().collect::<Vec<_>>();
-        let values = self.supported_tokens.values().cloned().collect::<Vec<_>>();
-
-        let start = from_index as u32;
-        let end = std::cmp::min(start + limit as u32, keys.len() as u32);
-
-        (start..end)
-            .map(|i| {
-                (
-                    keys.get(i as usize).cloned().unwrap(),
-                    values.get(i as usize).cloned().unwrap(),
-                )
-            })
-            .collect()
+    pub fn get_supported_tokens(
+        &self,
+        cursor: Option<AccountId>,
+        limit: u64,
+    ) -> Page<(AccountId, TokenInfo), AccountId> {
+        paginate_keys(
+            &self.supported_tokens,
+            cursor,
+            limit,
+            |token_id, token_info| Some((token_id.clone(), token_info.clone())),
+        )
     }
 
     pub fn is_linked(&self, platform: String, handle: String) -> bool {
         let social_handle = SocialHandle::new(platform, handle);
-        self.linked_accounts
-            .contains_key(&social_handle.to_string())
+        self.links.contains_key(&social_handle)
     }
 
-    pub fn get_claim_by_id(&self, claim_id: ClaimId) -> Option<Claim> {
-        self.claims_by_id.get(&claim_id).cloned()
+    pub fn get_claim_by_id(&self, claim_id: ClaimId) -> Option<ClaimExternal> {
+        self.claims_by_id
+            .get(&claim_id)
+            .map(|claim| self.format_claim(&claim_id, claim))
     }
 
     /// Get the linked account for a social handle
     pub fn get_linked_account(&self, platform: String, handle: String) -> Option<AccountId> {
         let social_handle = SocialHandle::new(platform, handle);
-        self.linked_accounts
-            .get(&social_handle.to_string())
-            .cloned()
+        self.linked_account(&social_handle).cloned()
     }
 
     /// Get the count of pending claims for a social handle
@@ -731,7 +1190,7 @@ impl Contract {
                 .iter()
                 .filter(|claim_id| {
                     if let Some(claim) = self.claims_by_id.get(claim_id) {
-                        !claim.claimed && !claim.is_expired()
+                        claim.is_claimable()
                     } else {
                         false
                     }
@@ -747,153 +1206,178 @@ impl Contract {
         &self,
         platform: String,
         handle: String,
-        from_index: u64,
+        cursor: Option<u64>,
         limit: u64,
-    ) -> Vec<ClaimExternal> {
+    ) -> Page<ClaimExternal> {
         let social_handle = SocialHandle::new(platform, handle);
+        self.claims_page(
+            self.handle_claims.get(&social_handle.to_string()),
+            cursor,
+            limit,
+            |claim_id, claim| {
+                claim
+                    .is_claimable()
+                    .then(|| self.format_claim(claim_id, claim))
+            },
+        )
+    }
 
-        if let Some(claim_ids) = self.handle_claims.get(&social_handle.to_string()) {
-            // First collect all claim IDs into a Vec
-            let claim_id_vec: Vec<ClaimId> = claim_ids.iter().cloned().collect();
-
-            // Apply pagination
-            let start = from_index as usize;
-            let end = std::cmp::min(start + limit as usize, claim_id_vec.len());
+    pub fn get_linked_handles(
+        &self,
+        cursor: Option<SocialHandle>,
+        limit: u64,
+    ) -> Page<(String, AccountId), SocialHandle> {
+        paginate_keys(&self.links, cursor, limit, |social_handle, record| {
+            Some((social_handle.to_string(), record.account_id.clone()))
+        })
+    }
 
-            if start < end {
-                // Map each claim ID to its corresponding claim
-                claim_id_vec[start..end]
-                    .iter()
-                    .filter_map(|claim_id| {
-                        let claim = self.claims_by_id.get(claim_id)?;
-                        if !claim.claimed && !claim.is_expired() {
-                            Some(format_claim(claim_id, claim))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect()
-            } else {
-                vec![]
-            }
-        } else {
-            vec![]
+    /// Get all social handles linked to a specific account
+    pub fn get_handles_by_account(
+        &self,
+        account_id: AccountId,
+        cursor: Option<SocialHandle>,
+        limit: u64,
+    ) -> Page<String, SocialHandle> {
+        match self.handles_by_account.get(&account_id) {
+            Some(handles) => paginate_keys(handles, cursor, limit, |social_handle, _| {
+                Some(social_handle.to_string())
+            }),
+            None => Page {
+                items: vec![],
+                next_cursor: None,
+            },
         }
     }
 
-    pub fn get_linked_handles(&self, from_index: u64, limit: u64) -> Vec<(String, AccountId)> {
-        let keys = self.linked_accounts.keys().collect::<Vec<_>>();
-        let values = self.linked_accounts.values().collect::<Vec<_>>();
+    /// Public view of a tipper's claims. Anonymous tips are left out, see `get_my_tips`
+    pub fn get_claims_by_tipper(
+        &self,
+        tipper: AccountId,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Page<ClaimExternal> {
+        self.claims_page(
+            self.claims_by_tipper.get(&tipper),
+            cursor,
+            limit,
+            |claim_id, claim| (!claim.is_anonymous()).then(|| self.format_claim(claim_id, claim)),
+        )
+    }
 
-        let start = from_index as usize;
-        let end = std::cmp::min(start + limit as usize, keys.len());
+    /// Claims made by the caller, including anonymous tips with the tipper revealed.
+    /// Called as a transaction so the tipper is authenticated by signature. The outcome is
+    /// public like any other transaction, so this only saves tippers an indexer.
+    pub fn get_my_tips(&mut self, cursor: Option<u64>, limit: u64) -> Page<ClaimExternal> {
+        let tipper = env::predecessor_account_id();
+        self.claims_page(
+            self.claims_by_tipper.get(&tipper),
+            cursor,
+            limit,
+            |claim_id, claim| {
+                Some(ClaimExternal {
+                    tipper: Some(tipper.clone()),
+                    ..self.format_claim(claim_id, claim)
+                })
+            },
+        )
+    }
 
-        if start < end {
-            (start..end)
-                .map(|i| (keys[i].clone(), values[i].clone()))
-                .collect()
-        } else {
-            vec![]
-        }
+    /// `get_claim_by_id` in the original flat format
+    pub fn get_claim_by_id_v1(&self, claim_id: ClaimId) -> Option<ClaimExternalV1> {
+        self.claims_by_id
+            .get(&claim_id)
+            .map(|claim| format_claim_v1(&claim_id, claim))
     }
 
-    /// Get all social handles linked to a specific account
-    pub fn get_handles_by_account(&self, account_id: AccountId) -> Vec<String> {
-        self.linked_accounts
-            .iter()
-            .filter(|(_, linked_account)| **linked_account == account_id)
-            .map(|(handle, _)| handle.clone())
+    /// `get_pending_claims` in the original flat format and offset pagination
+    pub fn get_pending_claims_v1(
+        &self,
+        platform: String,
+        handle: String,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<ClaimExternalV1> {
+        self.handle_claims_v1(SocialHandle::new(platform, handle), from_index, limit)
+            .filter(|(_, claim)| claim.is_claimable())
+            .map(|(claim_id, claim)| format_claim_v1(claim_id, claim))
             .collect()
     }
 
-    // pub fn get_claims_by_tipper(
-    //     &self,
-    //     tipper: AccountId,
-    //     from_index: u64,
-    //     limit: u64,
-    // ) -> Vec<Claim> {
-    //     let claims: Vec<Claim> = self
-    //         .claims_by_id
-    //         .iter()
-    //         .filter_map(|(_, claim)| {
-    //             if claim.tipper() == &tipper {
-    //                 Some(claim.clone())
-    //             } else {
-    //                 None
-    //             }
-    //         })
-    //         .collect();
-
-    //     let start = from_index as usize;
-    //     let end = std::cmp::min(start + limit as usize, claims.len());
-
-    //     if start < end {
-    //         claims[start..end].to_vec()
-    //     } else {
-    //         vec![]
-    //     }
-    // }
-
-    // inefficient
-    pub fn get_claims_by_tipper(
+    /// `get_all_claims_for_handle` in the original flat format and offset pagination
+    pub fn get_all_claims_for_handle_v1(
         &self,
-        tipper: AccountId,
+        platform: String,
+        handle: String,
         from_index: u64,
         limit: u64,
-    ) -> Vec<ClaimExternal> {
-        let claims: Vec<ClaimExternal> = self
-            .claims_by_id
-            .iter()
-            .filter_map(|(claim_id, claim)| {
-                if claim.tipper() == &tipper {
-                    Some(format_claim(claim_id, claim))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        let start = from_index as usize;
-        let end = std::cmp::min(start + limit as usize, claims.len());
+    ) -> Vec<ClaimExternalV1> {
+        self.handle_claims_v1(SocialHandle::new(platform, handle), from_index, limit)
+            .map(|(claim_id, claim)| format_claim_v1(claim_id, claim))
+            .collect()
+    }
 
-        if start < end {
-            claims[start..end].to_vec()
-        } else {
-            vec![]
-        }
+    fn handle_claims_v1(
+        &self,
+        social_handle: SocialHandle,
+        from_index: u64,
+        limit: u64,
+    ) -> impl Iterator<Item = (&ClaimId, &Claim)> {
+        self.handle_claims
+            .get(&social_handle.to_string())
+            .into_iter()
+            .flat_map(move |claim_ids| {
+                claim_ids
+                    .iter()
+                    .skip(from_index as usize)
+                    .take(limit as usize)
+            })
+            .filter_map(|claim_id| {
+                self.claims_by_id
+                    .get(claim_id)
+                    .map(|claim| (claim_id, claim))
+            })
     }
 
     pub fn get_all_claims_for_handle(
         &self,
         platform: String,
         handle: String,
-        from_index: u64,
+        cursor: Option<u64>,
         limit: u64,
-    ) -> Vec<ClaimExternal> {
+    ) -> Page<ClaimExternal> {
         let social_handle = SocialHandle::new(platform, handle);
+        self.claims_page(
+            self.handle_claims.get(&social_handle.to_string()),
+            cursor,
+            limit,
+            |claim_id, claim| Some(self.format_claim(claim_id, claim)),
+        )
+    }
 
-        if let Some(claim_ids) = self.handle_claims.get(&social_handle.to_string()) {
-            // First collect all claim IDs into a Vec
-            let claim_id_vec: Vec<ClaimId> = claim_ids.iter().cloned().collect();
-
-            // Apply pagination
-            let start = from_index as usize;
-            let end = std::cmp::min(start + limit as usize, claim_id_vec.len());
-
-            if start < end {
-                // Map each claim ID to its corresponding claim
-                claim_id_vec[start..end]
-                    .iter()
-                    .map(|claim_id| {
-                        let claim = self.claims_by_id.get(claim_id).unwrap(); // Unwrap is safe here because we know the claim_id exists
-                        format_claim(claim_id, claim)
-                    })
-                    .collect()
-            } else {
-                vec![]
-            }
-        } else {
-            vec![]
-        }
+    fn claims_page(
+        &self,
+        claim_ids: Option<&IterableSet<ClaimId>>,
+        cursor: Option<u64>,
+        limit: u64,
+        mut map: impl FnMut(&ClaimId, &Claim) -> Option<ClaimExternal>,
+    ) -> Page<ClaimExternal> {
+        let Some(claim_ids) = claim_ids else {
+            return Page {
+                items: vec![],
+                next_cursor: None,
+            };
+        };
+        paginate(
+            claim_ids.iter(),
+            claim_ids.len() as u64,
+            cursor,
+            limit,
+            |claim_id| {
+                self.claims_by_id
+                    .get(claim_id)
+                    .and_then(|claim| map(claim_id, claim))
+            },
+        )
     }
 }
@@ -0,0 +1,274 @@
+use crate::*;
+
+/// Details of a verified proof, captured when linking or re-verifying a handle
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct LinkProof {
+    /// Identifier of the Reclaim proof
+    pub proof_identifier: String,
+    pub provider: String,
+    /// Stable id of the platform user, which survives handle renames
+    pub platform_user_id: Option<String>,
+    pub display_name: Option<String>,
+}
+
+impl LinkProof {
+    pub fn from_proof(proof: &ReclaimProof) -> Self {
+        Self {
+            proof_identifier: proof.signedClaim.claim.identifier.clone(),
+            provider: proof.get_platform(),
+            platform_user_id: proof.extracted_parameter(&["user_id", "id_str", "id"]),
+            display_name: proof.extracted_parameter(&["name", "display_name"]),
+        }
+    }
+}
+
+/// New name of a renamed handle and the claims that move with it
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct HandleRename {
+    pub handle: SocialHandle,
+    /// Claims with a lower id existed at the rename and are collected through `handle`
+    pub claims_before: ClaimId,
+}
+
+/// A handle's link to a NEAR account
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct LinkRecord {
+    pub account_id: AccountId,
+    pub platform_user_id: Option<String>,
+    pub display_name: Option<String>,
+    pub linked_at: u64,
+    pub verified_at: u64,
+    /// Identifier of the Reclaim proof the link was last verified with
+    pub proof_identifier: String,
+    pub provider: String,
+    /// After this the link must be refreshed before it receives payouts
+    pub expires_at: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Re-verify a linked handle with a new proof, extending its link (linked account only)
+    #[payable]
+    pub fn refresh_link(
+        &mut self,
+        platform: String,
+        handle: String,
+        proof: ReclaimProof,
+    ) -> Promise {
+        self.assert_not_paused(Operation::Link);
+        require!(
+            env::attached_deposit() >= NearToken::from_yoctonear(1),
+            "Requires attached deposit"
+        );
+
+        let social_handle = SocialHandle::new(platform, handle);
+        let record = self
+            .links
+            .get(&social_handle)
+            .unwrap_or_else(|| env::panic_str("Handle is not linked"));
+        require!(
+            record.account_id == env::predecessor_account_id(),
+            "Only the linked account can refresh the link"
+        );
+        require!(proof.is_recent(env::block_timestamp()), "Proof is too old");
+        require!(
+            proof.signedClaim.claim.identifier != record.proof_identifier,
+            "Proof was already used for this link"
+        );
+        proof.assert_handle(&social_handle.handle);
+
+        let link_proof = LinkProof::from_proof(&proof);
+        require!(
+            record.platform_user_id.is_none()
+                || link_proof.platform_user_id.is_none()
+                || record.platform_user_id == link_proof.platform_user_id,
+            "Proof belongs to a different platform user"
+        );
+
+        external::ext_reclaim::ext(self.reclaim_contract_id.clone())
+            .with_static_gas(GAS_FOR_RECLAIM_VERIFY)
+            .verify_proof(proof)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Gas::from_tgas(5))
+                    .on_link_refreshed(social_handle, env::predecessor_account_id(), link_proof),
+            )
+    }
+
+    #[private]
+    pub fn on_link_refreshed(
+        &mut self,
+        social_handle: SocialHandle,
+        account_id: AccountId,
+        link_proof: LinkProof,
+        #[callback_result] verification_result: Result<(), PromiseError>,
+    ) {
+        if verification_result.is_err() {
+            env::panic_str("Proof verification failed")
+        }
+        // The link may have moved while the proof was being verified
+        require!(
+            self.linked_account(&social_handle) == Some(&account_id),
+            "Handle is no longer linked to this account"
+        );
+
+        let expires_at = self.internal_link(&social_handle, account_id.clone(), link_proof);
+        log_link_refreshed_event(
+            &social_handle.platform,
+            &social_handle.handle,
+            &account_id,
+            expires_at,
+        );
+    }
+
+    /// Set how long a link stays valid after it's verified, in nanoseconds (platform manager only)
+    pub fn set_link_validity_period(&mut self, period: u64) {
+        self.assert_role(Role::PlatformManager);
+        require!(period > 0, "Validity period must be greater than zero");
+        self.link_validity_period = period;
+
+        env::log_str(&format!("Link validity period changed to {}", period));
+    }
+
+    pub fn get_link_validity_period(&self) -> u64 {
+        self.link_validity_period
+    }
+
+    pub fn get_link_record(&self, platform: String, handle: String) -> Option<LinkRecord> {
+        self.links
+            .get(&SocialHandle::new(platform, handle))
+            .cloned()
+    }
+
+    /// New handle of a platform user that renamed away from this handle
+    pub fn get_renamed_handle(&self, platform: String, handle: String) -> Option<SocialHandle> {
+        self.renamed_handles
+            .get(&SocialHandle::new(platform, handle))
+            .map(|rename| rename.handle.clone())
+    }
+
+    /// Whether a linked handle needs a `refresh_link` before it receives payouts
+    pub fn is_link_stale(&self, platform: String, handle: String) -> bool {
+        self.link_stale(&SocialHandle::new(platform, handle))
+    }
+
+    pub(crate) fn linked_account(&self, social_handle: &SocialHandle) -> Option<&AccountId> {
+        self.links
+            .get(social_handle)
+            .map(|record| &record.account_id)
+    }
+
+    /// Handle whose link governs payouts: the handle itself, or its new name after a rename
+    /// along with the first claim id that no longer moves with the rename
+    pub(crate) fn resolve_handle(
+        &self,
+        social_handle: &SocialHandle,
+    ) -> (SocialHandle, Option<ClaimId>) {
+        if self.links.contains_key(social_handle) {
+            return (social_handle.clone(), None);
+        }
+        match self.renamed_handles.get(social_handle) {
+            Some(rename) => (rename.handle.clone(), Some(rename.claims_before)),
+            None => (social_handle.clone(), None),
+        }
+    }
+
+    pub(crate) fn link_stale(&self, social_handle: &SocialHandle) -> bool {
+        self.links
+            .get(social_handle)
+            .is_none_or(|record| env::block_timestamp() >= record.expires_at)
+    }
+
+    // Internal helper to link or re-verify a handle, returning the link's expiry.
+    // A platform user seen under another handle is treated as a rename: the old
+    // handle is unlinked and its existing claims become collectable through the new one
+    pub(crate) fn internal_link(
+        &mut self,
+        social_handle: &SocialHandle,
+        account_id: AccountId,
+        link_proof: LinkProof,
+    ) -> u64 {
+        let now = env::block_timestamp();
+        let expires_at = now + self.link_validity_period;
+        let linked_at = self
+            .links
+            .get(social_handle)
+            .filter(|record| record.account_id == account_id)
+            .map_or(now, |record| record.linked_at);
+
+        if let Some(user_id) = link_proof.platform_user_id.clone() {
+            let user_key = (social_handle.platform.clone(), user_id);
+            if let Some(previous_handle) = self
+                .handles_by_user_id
+                .insert(user_key, social_handle.clone())
+                .filter(|previous_handle| previous_handle != social_handle)
+            {
+                let still_owned = self
+                    .links
+                    .get(&previous_handle)
+                    .is_some_and(|record| record.platform_user_id == link_proof.platform_user_id);
+                if still_owned {
+                    self.internal_unlink(&previous_handle);
+                    self.renamed_handles.insert(
+                        previous_handle.clone(),
+                        HandleRename {
+                            handle: social_handle.clone(),
+                            claims_before: self.next_claim_id,
+                        },
+                    );
+                    log_handle_renamed_event(
+                        &social_handle.platform,
+                        &previous_handle.handle,
+                        &social_handle.handle,
+                        &account_id,
+                    );
+                }
+            }
+        }
+        // A handle that's linked again is no longer an alias of a renamed user
+        self.renamed_handles.remove(social_handle);
+
+        if self
+            .linked_account(social_handle)
+            .is_some_and(|previous_account| *previous_account != account_id)
+        {
+            self.internal_unlink(social_handle);
+        }
+        self.handles_by_account
+            .entry(account_id.clone())
+            .or_insert_with(|| {
+                TreeMap::new(StorageKey::AccountHandles {
+                    account_id: account_id.clone(),
+                })
+            })
+            .insert(social_handle.clone(), ());
+        self.links.insert(
+            social_handle.clone(),
+            LinkRecord {
+                account_id,
+                platform_user_id: link_proof.platform_user_id,
+                display_name: link_proof.display_name,
+                linked_at,
+                verified_at: now,
+                proof_identifier: link_proof.proof_identifier,
+                provider: link_proof.provider,
+                expires_at,
+            },
+        );
+        expires_at
+    }
+
+    // Internal helper to remove a handle's link along with its reverse index entry.
+    // The tip route was chosen by the unlinked account, so it goes too
+    pub(crate) fn internal_unlink(&mut self, social_handle: &SocialHandle) {
+        self.tip_routes.remove(&social_handle.to_string());
+        if let Some(record) = self.links.remove(social_handle) {
+            if let Some(handles) = self.handles_by_account.get_mut(&record.account_id) {
+                handles.remove(social_handle);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::*;
+use near_sdk::borsh::BorshDeserialize;
+
+/// Most entries moved by one `migrate_batch` call
+const MAX_MIGRATION_BATCH: u64 = 50;
+
+/// Claim as stored by the original release
+#[near(serializers=[borsh])]
+pub struct ClaimV0 {
+    pub claim_type: ClaimType,
+    pub amount: NearToken,
+    pub tipper: AccountId,
+    pub recipient: String,
+    pub timestamp: u64,
+    pub expires_at: u64,
+    pub claimed: bool,
+}
+
+/// Token info as stored by the original release
+#[near(serializers=[borsh])]
+pub struct TokenInfoV0 {
+    pub standard: TokenStandard,
+    pub decimals: u8,
+    pub symbol: String,
+    pub chain: String,
+}
+
+/// Contract state of the original release
+#[near(serializers=[borsh])]
+pub struct ContractV0 {
+    pub owner_id: AccountId,
+    pub reclaim_contract_id: AccountId,
+    pub linked_accounts: IterableMap<String, AccountId>,
+    pub next_claim_id: ClaimId,
+    pub claims_by_id: IterableMap<ClaimId, ClaimV0>,
+    pub handle_claims: IterableMap<String, IterableSet<ClaimId>>,
+    pub supported_tokens: IterableMap<AccountId, TokenInfoV0>,
+    pub paused: bool,
+}
+
+/// Original release collections still waiting to be migrated
+#[near(serializers=[borsh])]
+pub struct LegacyState {
+    pub linked_accounts: IterableMap<String, AccountId>,
+    pub claims_by_id: IterableMap<ClaimId, ClaimV0>,
+    pub supported_tokens: IterableMap<AccountId, TokenInfoV0>,
+    /// Whether the old contract was paused, restored once the migration completes
+    pub paused: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Upgrade the state left by the original release and move a first batch of it. The
+    /// rest is moved by `migrate_batch`; operations stay paused until it completes.
+    /// State already in the current layout is kept as is, so every upgrade can call this
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let state = env::storage_read(b"STATE")
+            .unwrap_or_else(|| env::panic_str("Contract is not initialized"));
+        if let Ok(contract) = Contract::try_from_slice(&state) {
+            return contract;
+        }
+        let old = ContractV0::try_from_slice(&state)
+            .unwrap_or_else(|_| env::panic_str("Unknown state layout"));
+
+        let mut contract = Self::internal_new(old.owner_id, old.reclaim_contract_id);
+        contract.next_claim_id = old.next_claim_id;
+        contract.handle_claims = old.handle_claims;
+        contract.legacy = Some(LegacyState {
+            linked_accounts: old.linked_accounts,
+            claims_by_id: old.claims_by_id,
+            supported_tokens: old.supported_tokens,
+            paused: old.paused,
+        });
+        contract.internal_migrate_batch(MAX_MIGRATION_BATCH);
+
+        contract
+    }
+
+    /// Move up to `limit` more entries of the original release state (upgrader only).
+    /// Returns true once the migration is complete
+    pub fn migrate_batch(&mut self, limit: Option<u64>) -> bool {
+        self.assert_role(Role::Upgrader);
+        require!(self.legacy.is_some(), "No migration in progress");
+
+        let limit = limit
+            .unwrap_or(MAX_MIGRATION_BATCH)
+            .min(MAX_MIGRATION_BATCH);
+        self.internal_migrate_batch(limit)
+    }
+
+    pub fn is_migrating(&self) -> bool {
+        self.legacy.is_some()
+    }
+
+    // Moves tokens, then links, then claims out of the legacy collections. Once they are
+    // empty the legacy state is dropped, which lifts the migration pause
+    fn internal_migrate_batch(&mut self, limit: u64) -> bool {
+        let Some(mut legacy) = self.legacy.take() else {
+            return true;
+        };
+        let mut remaining = limit as usize;
+
+        let token_ids: Vec<AccountId> = legacy
+            .supported_tokens
+            .keys()
+            .take(remaining)
+            .cloned()
+            .collect();
+        remaining -= token_ids.len();
+        for token_id in token_ids {
+            let info = legacy.supported_tokens.remove(&token_id).unwrap();
+            self.supported_tokens.insert(
+                token_id,
+                TokenInfo {
+                    standard: info.standard,
+                    decimals: info.decimals,
+                    symbol: info.symbol,
+                    chain: info.chain,
+                    paused: false,
+                    expiry: ExpiryConfig::default(),
+                },
+            );
+        }
+
+        let keys: Vec<String> = legacy
+            .linked_accounts
+            .keys()
+            .take(remaining)
+            .cloned()
+            .collect();
+        remaining -= keys.len();
+        for key in keys {
+            let account_id = legacy.linked_accounts.remove(&key).unwrap();
+            if let Some((platform, handle)) = key.split_once(':') {
+                let social_handle = SocialHandle::new(platform.to_string(), handle.to_string());
+                self.internal_migrate_link(social_handle, account_id);
+            }
+        }
+
+        let claim_ids: Vec<ClaimId> = legacy
+            .claims_by_id
+            .keys()
+            .take(remaining)
+            .cloned()
+            .collect();
+        for claim_id in claim_ids {
+            let old_claim = legacy.claims_by_id.remove(&claim_id).unwrap();
+            self.internal_migrate_claim(claim_id, old_claim);
+        }
+
+        let done = legacy.supported_tokens.is_empty()
+            && legacy.linked_accounts.is_empty()
+            && legacy.claims_by_id.is_empty();
+        if done {
+            if legacy.paused {
+                for operation in ALL_OPERATIONS {
+                    self.paused_operations.insert(operation);
+                }
+            }
+            env::log_str("Migration complete");
+        } else {
+            self.legacy = Some(legacy);
+        }
+        done
+    }
+
+    // Old links carry no proof details and must be refreshed once they expire
+    fn internal_migrate_link(&mut self, social_handle: SocialHandle, account_id: AccountId) {
+        let now = env::block_timestamp();
+        self.handles_by_account
+            .entry(account_id.clone())
+            .or_insert_with(|| {
+                TreeMap::new(StorageKey::AccountHandles {
+                    account_id: account_id.clone(),
+                })
+            })
+            .insert(social_handle.clone(), ());
+        self.links.insert(
+            social_handle.clone(),
+            LinkRecord {
+                account_id,
+                platform_user_id: None,
+                display_name: None,
+                linked_at: now,
+                verified_at: now,
+                proof_identifier: String::new(),
+                provider: social_handle.platform,
+                expires_at: now + self.link_validity_period,
+            },
+        );
+    }
+
+    fn internal_migrate_claim(&mut self, claim_id: ClaimId, old_claim: ClaimV0) {
+        let claim = Claim {
+            claim_type: old_claim.claim_type,
+            amount: old_claim.amount,
+            tipper: old_claim.tipper,
+            recipient: old_claim.recipient,
+            timestamp: old_claim.timestamp,
+            expires_at: old_claim.expires_at,
+            status: if old_claim.claimed {
+                ClaimStatus::Claimed
+            } else {
+                ClaimStatus::Pending
+            },
+            tipper_commitment: None,
+            arbiter: None,
+            campaign_id: None,
+            memo: None,
+            cancellable: false,
+            rounds: vec![],
+        };
+        if claim.status == ClaimStatus::Pending {
+            self.internal_escrow_claim(&claim);
+        }
+        self.claims_by_tipper
+            .entry(claim.tipper.clone())
+            .or_insert_with(|| {
+                IterableSet::new(StorageKey::TipperClaims {
+                    tipper: claim.tipper.clone(),
+                })
+            })
+            .insert(claim_id);
+        self.claims_by_id.insert(claim_id, claim);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Why and by whom a handle or account was blocked
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct BlockRecord {
+    pub reason: String,
+    pub moderator: AccountId,
+    pub blocked_at: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Block a handle from being linked or tipped. Pending claims of a linked handle are
+    /// frozen until it is unblocked (moderator only)
+    pub fn block_handle(&mut self, platform: String, handle: String, reason: String) {
+        self.assert_role(Role::Moderator);
+        let social_handle = SocialHandle::new(platform, handle);
+        let record = BlockRecord {
+            reason,
+            moderator: env::predecessor_account_id(),
+            blocked_at: env::block_timestamp(),
+        };
+
+        log_handle_blocked_event(
+            &social_handle.platform,
+            &social_handle.handle,
+            &record.reason,
+            &record.moderator,
+        );
+        self.blocked_handles
+            .insert(social_handle.to_string(), record);
+    }
+
+    /// Lift a handle block, unfreezing its pending claims (moderator only)
+    pub fn unblock_handle(&mut self, platform: String, handle: String, reason: String) {
+        self.assert_role(Role::Moderator);
+        let social_handle = SocialHandle::new(platform, handle);
+        require!(
+            self.blocked_handles
+                .remove(&social_handle.to_string())
+                .is_some(),
+            "Handle is not blocked"
+        );
+
+        log_handle_unblocked_event(
+            &social_handle.platform,
+            &social_handle.handle,
+            &reason,
+            &env::predecessor_account_id(),
+        );
+    }
+
+    /// Block an account from tipping (moderator only)
+    pub fn block_account(&mut self, account_id: AccountId, reason: String) {
+        self.assert_role(Role::Moderator);
+        let record = BlockRecord {
+            reason,
+            moderator: env::predecessor_account_id(),
+            blocked_at: env::block_timestamp(),
+        };
+
+        log_account_blocked_event(&account_id, &record.reason, &record.moderator);
+        self.blocked_accounts.insert(account_id, record);
+    }
+
+    pub fn unblock_account(&mut self, account_id: AccountId, reason: String) {
+        self.assert_role(Role::Moderator);
+        require!(
+            self.blocked_accounts.remove(&account_id).is_some(),
+            "Account is not blocked"
+        );
+
+        log_account_unblocked_event(&account_id, &reason, &env::predecessor_account_id());
+    }
+
+    pub fn get_handle_block(&self, platform: String, handle: String) -> Option<BlockRecord> {
+        self.blocked_handles
+            .get(&SocialHandle::new(platform, handle).to_string())
+            .cloned()
+    }
+
+    pub fn get_blocked_handles(
+        &self,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Page<(String, BlockRecord), String> {
+        paginate_keys(&self.blocked_handles, cursor, limit, |handle, record| {
+            Some((handle.clone(), record.clone()))
+        })
+    }
+
+    pub fn get_account_block(&self, account_id: AccountId) -> Option<BlockRecord> {
+        self.blocked_accounts.get(&account_id).cloned()
+    }
+
+    pub fn get_blocked_accounts(
+        &self,
+        cursor: Option<AccountId>,
+        limit: u64,
+    ) -> Page<(AccountId, BlockRecord), AccountId> {
+        paginate_keys(
+            &self.blocked_accounts,
+            cursor,
+            limit,
+            |account_id, record| Some((account_id.clone(), record.clone())),
+        )
+    }
+
+    pub(crate) fn assert_handle_not_blocked(&self, social_handle: &SocialHandle) {
+        require!(
+            !self
+                .blocked_handles
+                .contains_key(&social_handle.to_string()),
+            "Handle is blocked"
+        );
+    }
+
+    pub(crate) fn assert_account_not_blocked(&self, account_id: &AccountId) {
+        require!(
+            !self.blocked_accounts.contains_key(account_id),
+            "Account is blocked"
+        );
+    }
+}
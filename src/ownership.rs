@@ -0,0 +1,95 @@
+use crate::*;
+
+/// An ownership transfer waiting for the new owner to accept
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct PendingOwner {
+    pub account_id: AccountId,
+    pub proposed_at: u64,
+    /// Earliest time the new owner can accept
+    pub accept_after: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Propose a new owner (owner only). It must call `accept_ownership` to take over,
+    /// no earlier than `ownership_transfer_delay` after the proposal
+    pub fn propose_owner(&mut self, new_owner: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only owner can propose a new owner"
+        );
+        require!(new_owner != self.owner_id, "Account is already the owner");
+
+        let now = env::block_timestamp();
+        let pending = PendingOwner {
+            account_id: new_owner,
+            proposed_at: now,
+            accept_after: now + self.ownership_transfer_delay,
+        };
+        log_ownership_proposed_event(&self.owner_id, &pending.account_id, pending.accept_after);
+        self.pending_owner = Some(pending);
+    }
+
+    /// Accept a pending ownership transfer (proposed owner only)
+    pub fn accept_ownership(&mut self) {
+        let pending = self
+            .pending_owner
+            .take()
+            .unwrap_or_else(|| env::panic_str("No pending ownership transfer"));
+        require!(
+            env::predecessor_account_id() == pending.account_id,
+            "Only the proposed owner can accept"
+        );
+        require!(
+            env::block_timestamp() >= pending.accept_after,
+            "Ownership transfer delay has not passed"
+        );
+
+        let previous_owner = std::mem::replace(&mut self.owner_id, pending.account_id);
+        log_ownership_transferred_event(&previous_owner, &self.owner_id);
+    }
+
+    /// Cancel a pending ownership transfer (owner only)
+    pub fn cancel_ownership_transfer(&mut self) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only owner can cancel an ownership transfer"
+        );
+        let pending = self
+            .pending_owner
+            .take()
+            .unwrap_or_else(|| env::panic_str("No pending ownership transfer"));
+
+        log_ownership_transfer_cancelled_event(&self.owner_id, &pending.account_id);
+    }
+
+    /// Raise the delay in nanoseconds between proposing and accepting ownership (owner only).
+    /// Applies to proposals made after the change. Lowering it goes through the timelock
+    /// as `AdminAction::SetOwnershipTransferDelay`
+    pub fn set_ownership_transfer_delay(&mut self, delay: u64) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only owner can change the ownership transfer delay"
+        );
+        require!(
+            delay >= self.ownership_transfer_delay,
+            "Lowering the delay must be queued as an admin action"
+        );
+        self.ownership_transfer_delay = delay;
+
+        env::log_str(&format!("Ownership transfer delay changed to {}", delay));
+    }
+
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<PendingOwner> {
+        self.pending_owner.clone()
+    }
+
+    pub fn get_ownership_transfer_delay(&self) -> u64 {
+        self.ownership_transfer_delay
+    }
+}
@@ -0,0 +1,83 @@
+use crate::*;
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use std::ops::Bound;
+
+/// Maximum records a filtered page reads before returning early with a cursor
+const MAX_PAGE_SCAN: u64 = 500;
+
+/// A page of results. Pass `next_cursor` back to continue, None means the end was reached.
+/// The cursor is a position for append-only collections and the last key read otherwise
+#[near(serializers=[json])]
+pub struct Page<T, C = u64> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<C>,
+}
+
+/// Collect up to `limit` items from a collection of `len` records, starting at position
+/// `cursor`. `map` returns None for records filtered out of the page. Only the records
+/// scanned are read, and at most `MAX_PAGE_SCAN` of them
+pub(crate) fn paginate<I, T>(
+    records: I,
+    len: u64,
+    cursor: Option<u64>,
+    limit: u64,
+    mut map: impl FnMut(I::Item) -> Option<T>,
+) -> Page<T>
+where
+    I: Iterator,
+{
+    let mut position = cursor.unwrap_or(0);
+    let mut items = vec![];
+    let scan_end = position.saturating_add(MAX_PAGE_SCAN).min(len);
+
+    let mut records = records.skip(position as usize);
+    while (items.len() as u64) < limit && position < scan_end {
+        let Some(record) = records.next() else {
+            break;
+        };
+        position += 1;
+        if let Some(item) = map(record) {
+            items.push(item);
+        }
+    }
+
+    Page {
+        items,
+        next_cursor: (position < len).then_some(position),
+    }
+}
+
+/// Collect up to `limit` items of an ordered map, starting after the key `cursor`.
+/// Keys stay valid as cursors when records are inserted or removed between pages
+pub(crate) fn paginate_keys<K, V, T>(
+    records: &TreeMap<K, V>,
+    cursor: Option<K>,
+    limit: u64,
+    mut map: impl FnMut(&K, &V) -> Option<T>,
+) -> Page<T, K>
+where
+    K: Ord + Clone + BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+{
+    let start = cursor.as_ref().map_or(Bound::Unbounded, Bound::Excluded);
+    let mut records = records.range((start, Bound::Unbounded)).peekable();
+    let mut items = vec![];
+    let mut last_key = cursor.clone();
+    let mut scanned = 0;
+
+    while (items.len() as u64) < limit && scanned < MAX_PAGE_SCAN {
+        let Some((key, value)) = records.next() else {
+            break;
+        };
+        scanned += 1;
+        last_key = Some(key.clone());
+        if let Some(item) = map(key, value) {
+            items.push(item);
+        }
+    }
+
+    Page {
+        items,
+        next_cursor: records.peek().and(last_key),
+    }
+}
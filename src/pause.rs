@@ -0,0 +1,95 @@
+use crate::*;
+
+/// User-facing operations that can be paused independently
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Operation {
+    Link,
+    TipNear,
+    FtTip,
+    NftTip,
+    Claim,
+    Reclaim,
+    Sweep,
+}
+
+pub(crate) const ALL_OPERATIONS: [Operation; 7] = [
+    Operation::Link,
+    Operation::TipNear,
+    Operation::FtTip,
+    Operation::NftTip,
+    Operation::Claim,
+    Operation::Reclaim,
+    Operation::Sweep,
+];
+
+#[near_bindgen]
+impl Contract {
+    /// Pause the given operations, or all of them when none are given (pauser only)
+    pub fn pause(&mut self, operations: Option<Vec<Operation>>) {
+        self.assert_role(Role::Pauser);
+
+        let operations = operations.unwrap_or_else(|| ALL_OPERATIONS.to_vec());
+        for operation in operations.iter() {
+            self.paused_operations.insert(*operation);
+        }
+
+        log_operations_paused_event(&operations, &env::predecessor_account_id());
+    }
+
+    /// Unpause the given operations, or all of them when none are given (pauser only)
+    pub fn unpause(&mut self, operations: Option<Vec<Operation>>) {
+        self.assert_role(Role::Pauser);
+
+        let operations = operations.unwrap_or_else(|| ALL_OPERATIONS.to_vec());
+        for operation in operations.iter() {
+            self.paused_operations.remove(operation);
+        }
+
+        log_operations_unpaused_event(&operations, &env::predecessor_account_id());
+    }
+
+    /// Pause or unpause tips in a supported token (pauser only)
+    pub fn set_token_paused(&mut self, token_id: AccountId, paused: bool) {
+        self.assert_role(Role::Pauser);
+
+        let token_info = self
+            .supported_tokens
+            .get_mut(&token_id)
+            .unwrap_or_else(|| env::panic_str("Unsupported token"));
+        token_info.paused = paused;
+
+        log_token_paused_event(&token_id, paused, &env::predecessor_account_id());
+    }
+
+    /// Check if any operation is paused
+    pub fn is_paused(&self) -> bool {
+        !self.paused_operations.is_empty()
+    }
+
+    pub fn is_operation_paused(&self, operation: Operation) -> bool {
+        self.paused_operations.contains(&operation)
+    }
+
+    pub fn get_paused_operations(&self) -> Vec<Operation> {
+        self.paused_operations.iter().copied().collect()
+    }
+
+    pub(crate) fn assert_not_paused(&self, operation: Operation) {
+        require!(self.legacy.is_none(), "Contract is migrating");
+        require!(
+            !self.paused_operations.contains(&operation),
+            format!("{:?} is paused", operation)
+        );
+    }
+
+    pub(crate) fn assert_token_not_paused(&self, token_id: &AccountId) {
+        require!(
+            !self
+                .supported_tokens
+                .get(token_id)
+                .is_some_and(|info| info.paused),
+            "Token is paused"
+        );
+    }
+}
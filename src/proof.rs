@@ -8,8 +8,10 @@ pub struct ClaimInfo {
     pub context: String,    // Additional context
 }
 
+// Field names follow the Reclaim Protocol proof format
 #[near(serializers=[borsh, json])]
 #[derive(Clone, PartialEq, Eq)]
+#[allow(non_snake_case)]
 pub struct CompleteClaimData {
     pub identifier: String,
     pub owner: String,
@@ -26,6 +28,7 @@ pub struct SignedClaim {
 
 #[near(serializers=[borsh, json])]
 #[derive(Clone, PartialEq, Eq)]
+#[allow(non_snake_case)]
 pub struct ReclaimProof {
     pub claimInfo: ClaimInfo,
     pub signedClaim: SignedClaim,
@@ -38,6 +41,44 @@ impl ReclaimProof {
         current_time.saturating_sub(proof_time_ns) < crate::MAX_PROOF_AGE
     }
 
+    /// Panics unless the proven screen name matches `handle`, ignoring case since
+    /// handles are stored lowercased
+    pub fn assert_handle(&self, handle: &str) {
+        match serde_json::from_str::<serde_json::Value>(&self.claimInfo.context) {
+            Ok(context_json) => {
+                if let Some(extracted_param) = context_json.get("extractedParameters") {
+                    if let Some(screen_name_value) = extracted_param.get("screen_name") {
+                        if let Some(screen_name) = screen_name_value.as_str() {
+                            env::log_str(&format!("screen_name: {}, {}", screen_name, handle));
+                            require!(
+                                screen_name.eq_ignore_ascii_case(handle),
+                                "Proven handle does not match passed handle"
+                            );
+                        }
+                    } else {
+                        env::panic_str("screen_name not found in extractedParameters");
+                    }
+                } else {
+                    env::panic_str("needed params not found");
+                }
+            }
+            Err(_) => env::panic_str("Failed to parse context Json for verification"),
+        }
+    }
+
+    /// First of `keys` present in the proof's extracted parameters
+    pub fn extracted_parameter(&self, keys: &[&str]) -> Option<String> {
+        let context_json =
+            serde_json::from_str::<serde_json::Value>(&self.claimInfo.context).ok()?;
+        let extracted_params = context_json.get("extractedParameters")?;
+        keys.iter()
+            .find_map(|key| match extracted_params.get(*key)? {
+                serde_json::Value::String(value) => Some(value.clone()),
+                serde_json::Value::Number(value) => Some(value.to_string()),
+                _ => None,
+            })
+    }
+
     pub fn get_platform(&self) -> String {
         self.claimInfo.provider.clone()
     }
@@ -0,0 +1,132 @@
+use crate::*;
+
+/// Maximum claims reclaimed per `reclaim_all` call
+const MAX_RECLAIMS_PER_CALL: u64 = 20;
+/// Maximum of the tipper's claims scanned per `reclaim_all` call
+const MAX_RECLAIM_SCAN: usize = 200;
+
+/// Claims settled by one transfer of a `reclaim_all` call
+#[near(serializers=[json])]
+pub struct ReclaimBatch {
+    pub claims: Vec<ClaimId>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Reclaim up to `limit` of the caller's expired or rejected tips. NEAR and each FT are
+    /// returned in one transfer per token, NFTs one by one, settled by a single callback.
+    /// Scans at most `MAX_RECLAIM_SCAN` of the caller's tips, starting at position `cursor`
+    pub fn reclaim_all(&mut self, cursor: Option<u64>, limit: Option<u64>) -> Promise {
+        self.assert_not_paused(Operation::Reclaim);
+
+        let tipper = env::predecessor_account_id();
+        let limit = limit
+            .unwrap_or(MAX_RECLAIMS_PER_CALL)
+            .min(MAX_RECLAIMS_PER_CALL) as usize;
+        let reclaimable: Vec<(ClaimId, Claim)> = self
+            .claims_by_tipper
+            .get(&tipper)
+            .map(|claim_ids| {
+                claim_ids
+                    .iter()
+                    .skip(cursor.unwrap_or(0) as usize)
+                    .take(MAX_RECLAIM_SCAN)
+                    .filter_map(|claim_id| {
+                        self.claims_by_id
+                            .get(claim_id)
+                            .filter(|claim| claim.is_reclaimable())
+                            .map(|claim| (*claim_id, claim.clone()))
+                    })
+                    .take(limit)
+                    .collect()
+            })
+            .unwrap_or_default();
+        require!(!reclaimable.is_empty(), "No reclaimable tips");
+
+        // NEAR and FT claims share one transfer per token, NFTs get one each
+        let mut transfers: Vec<(ClaimType, u128, ReclaimBatch)> = Vec::new();
+        for (claim_id, claim) in reclaimable {
+            match transfers.iter_mut().find(|(claim_type, _, _)| {
+                claim_type == &claim.claim_type
+                    && !matches!(claim_type, ClaimType::NonFungibleToken { .. })
+            }) {
+                Some((_, amount, batch)) => {
+                    *amount += claim.amount();
+                    batch.claims.push(claim_id);
+                }
+                None => transfers.push((
+                    claim.claim_type.clone(),
+                    claim.amount(),
+                    ReclaimBatch {
+                        claims: vec![claim_id],
+                    },
+                )),
+            }
+            // Locked so the claim can't be reclaimed twice while in flight
+            self.internal_lock_claim(claim_id, &claim, tipper.clone(), SettlementKind::Reclaim);
+        }
+
+        let mut batches = Vec::new();
+        let mut joined: Option<Promise> = None;
+        for (claim_type, amount, batch) in transfers {
+            let transfer = claim_type.transfer(
+                tipper.clone(),
+                NearToken::from_yoctonear(amount),
+                "Reclaimed expired tips".to_string(),
+            );
+            joined = Some(match joined {
+                Some(joined) => joined.and(transfer),
+                None => transfer,
+            });
+            batches.push(batch);
+        }
+
+        joined.unwrap().then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(Gas::from_tgas(20))
+                .on_reclaim_all_complete(batches),
+        )
+    }
+
+    /// Settles each batch whose transfer succeeded and queues the claims of failed ones
+    /// for `retry_settlement`
+    #[private]
+    pub fn on_reclaim_all_complete(&mut self, batches: Vec<ReclaimBatch>) {
+        for (index, batch) in batches.into_iter().enumerate() {
+            let succeeded = matches!(
+                env::promise_result(index as u64),
+                PromiseResult::Successful(_)
+            );
+            for claim_id in batch.claims {
+                if !succeeded {
+                    env::log_str(&format!("Reclaim of claim {} failed", claim_id));
+                    self.internal_settlement_failed(
+                        claim_id,
+                        format!("Reclaim transfer for claim {} failed", claim_id),
+                    );
+                    continue;
+                }
+                if self.settlements.remove(&claim_id).is_none() {
+                    continue;
+                }
+                let Some(mut claim) = self.claims_by_id.get(&claim_id).cloned() else {
+                    continue;
+                };
+
+                claim.status = ClaimStatus::Reclaimed;
+                self.claims_by_id.insert(claim_id, claim.clone());
+                self.internal_release_claim(&claim, claim.amount());
+                self.internal_record_settlement_stats(&claim, claim.amount(), true);
+                self.internal_reverse_round_contribution(&claim);
+                let social_handle = claim.social_handle();
+                log_tip_reclaimed_event(
+                    &social_handle.platform,
+                    &social_handle.handle,
+                    claim.amount().into(),
+                    claim.token_type(),
+                    &claim.public_tipper(),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::*;
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    /// Can pause and unpause the contract
+    Pauser,
+    /// Can register and remove supported tokens
+    TokenManager,
+    /// Can change the Reclaim verifier contract
+    PlatformManager,
+    /// Can change protocol fees
+    FeeManager,
+    /// Can deploy new contract code
+    Upgrader,
+    /// Can cancel queued admin actions and rescue unbacked assets
+    Guardian,
+    /// Can block handles and accounts
+    Moderator,
+    /// Can resolve link disputes
+    Arbiter,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Grant a role to an account (owner only)
+    pub fn grant_role(&mut self, role: Role, account_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only owner can grant roles"
+        );
+
+        let previous = self
+            .role_members
+            .entry(role)
+            .or_insert_with(|| TreeMap::new(StorageKey::RoleMembersByRole { role }))
+            .insert(account_id.clone(), ());
+        require!(previous.is_none(), "Account already has this role");
+
+        log_role_granted_event(role, &account_id, &env::predecessor_account_id());
+    }
+
+    /// Revoke a role from an account (owner only)
+    pub fn revoke_role(&mut self, role: Role, account_id: AccountId) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only owner can revoke roles"
+        );
+
+        let removed = self
+            .role_members
+            .get_mut(&role)
+            .is_some_and(|members| members.remove(&account_id).is_some());
+        require!(removed, "Account does not have this role");
+
+        log_role_revoked_event(role, &account_id, &env::predecessor_account_id());
+    }
+
+    /// Whether an account holds a role. The owner holds every role
+    pub fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        account_id == self.owner_id
+            || self
+                .role_members
+                .get(&role)
+                .is_some_and(|members| members.contains_key(&account_id))
+    }
+
+    pub fn get_role_members(
+        &self,
+        role: Role,
+        cursor: Option<AccountId>,
+        limit: u64,
+    ) -> Page<AccountId, AccountId> {
+        match self.role_members.get(&role) {
+            Some(members) => paginate_keys(members, cursor, limit, |account_id, _| {
+                Some(account_id.clone())
+            }),
+            None => Page {
+                items: vec![],
+                next_cursor: None,
+            },
+        }
+    }
+
+    /// Deploy new contract code passed as the raw call input and run `migrate` (upgrader only).
+    /// The code's hash must be queued as an `UpgradeContract` action that is past its timelock
+    pub fn upgrade(&mut self) -> Promise {
+        self.assert_role(Role::Upgrader);
+        let code = env::input().unwrap_or_else(|| env::panic_str("Missing contract code"));
+        require!(!code.is_empty(), "Missing contract code");
+
+        let code_hash = env::sha256_array(&code);
+        let now = env::block_timestamp();
+        let (action_id, queued) = self
+            .queued_actions
+            .iter()
+            .find(|(_, queued)| {
+                matches!(&queued.action, AdminAction::UpgradeContract { code_hash: queued_hash }
+                    if CryptoHash::from(*queued_hash) == code_hash)
+                    && now >= queued.executable_at
+            })
+            .map(|(action_id, queued)| (*action_id, queued.clone()))
+            .unwrap_or_else(|| env::panic_str("No executable upgrade queued for this code"));
+        self.queued_actions.remove(&action_id);
+        log_admin_action_executed_event(action_id, &queued.action);
+
+        env::log_str(&format!(
+            "Contract upgraded by {}",
+            env::predecessor_account_id()
+        ));
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                "migrate".to_string(),
+                vec![],
+                NearToken::from_yoctonear(0),
+                GAS_FOR_MIGRATE,
+            )
+    }
+
+    pub(crate) fn assert_role(&self, role: Role) {
+        require!(
+            self.has_role(role, env::predecessor_account_id()),
+            format!("Requires {:?} role", role)
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use crate::*;
+
+pub type RoundId = u64;
+
+/// Maximum handles paid out per `finalize_round` call
+const MAX_HANDLES_PER_FINALIZE: u64 = 50;
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct Round {
+    /// Account that created the round; it can reclaim matches that are never claimed
+    pub sponsor: AccountId,
+    /// Token of the matching pool, `near` for NEAR. Only tips in this token are matched
+    pub token_id: AccountId,
+    pub start: u64,
+    pub end: u64,
+    pub matching_pool: U128,
+    /// Sum of all handles' quadratic funding scores, kept up to date on every tip
+    pub total_score: U128,
+    pub distributed: U128,
+    /// Number of handles already paid out by `finalize_round`
+    pub finalize_cursor: u64,
+    pub finalized: bool,
+}
+
+/// Contributions to one handle within a round
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Default)]
+pub struct HandleMatch {
+    pub total: U128,
+    /// Sum of the square roots of each unique tipper's total contribution
+    pub sqrt_sum: U128,
+    pub contributors: u64,
+}
+
+impl HandleMatch {
+    /// Quadratic funding score: (sum of square roots)^2 minus the sum of contributions,
+    /// saturating at `u128::MAX`
+    pub fn score(&self) -> u128 {
+        self.sqrt_sum
+            .0
+            .saturating_mul(self.sqrt_sum.0)
+            .saturating_sub(self.total.0)
+    }
+}
+
+#[near(serializers=[json])]
+pub struct RoundExternal {
+    pub id: RoundId,
+    pub sponsor: AccountId,
+    pub token_id: AccountId,
+    pub start: u64,
+    pub end: u64,
+    pub matching_pool: U128,
+    pub total_score: U128,
+    pub distributed: U128,
+    pub handles: u64,
+    pub finalized: bool,
+}
+
+impl Round {
+    pub fn is_active(&self) -> bool {
+        let now = env::block_timestamp();
+        self.start <= now && now < self.end
+    }
+
+    /// Share of the matching pool for a handle with the given score
+    pub fn match_for(&self, score: u128) -> u128 {
+        if self.total_score.0 == 0 {
+            return 0;
+        }
+        mul_div(self.matching_pool.0, score, self.total_score.0)
+    }
+}
+
+/// Integer square root (floor)
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// `a * b / c` without overflowing, dropping low bits of `b` and `c` when needed
+fn mul_div(a: u128, mut b: u128, mut c: u128) -> u128 {
+    while b != 0 && a.checked_mul(b).is_none() {
+        b >>= 1;
+        c >>= 1;
+    }
+    if c == 0 {
+        return 0;
+    }
+    a * b / c
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Create a quadratic funding round (platform manager only). A NEAR pool is funded by the attached
+    /// deposit; FT pools are funded with `ft_transfer_call` and `{"fund_round": id}` as msg
+    #[payable]
+    pub fn create_round(&mut self, token_id: Option<AccountId>, start: u64, end: u64) -> RoundId {
+        self.assert_role(Role::PlatformManager);
+        require!(start < end, "Round must end after it starts");
+        require!(
+            end > env::block_timestamp(),
+            "Round end must be in the future"
+        );
+
+        let token_id = token_id.unwrap_or_else(near_token_id);
+        require!(
+            token_id == near_token_id()
+                || self
+                    .supported_tokens
+                    .get(&token_id)
+                    .is_some_and(|info| info.standard == TokenStandard::NEP141),
+            "Unsupported token"
+        );
+        let deposit = env::attached_deposit().as_yoctonear();
+        require!(
+            token_id == near_token_id() || deposit == 0,
+            "FT rounds are funded with ft_transfer_call"
+        );
+
+        let round_id = self.next_round_id;
+        self.next_round_id += 1;
+        self.rounds.insert(
+            round_id,
+            Round {
+                sponsor: env::predecessor_account_id(),
+                token_id: token_id.clone(),
+                start,
+                end,
+                matching_pool: U128(deposit),
+                total_score: U128(0),
+                distributed: U128(0),
+                finalize_cursor: 0,
+                finalized: false,
+            },
+        );
+        self.active_rounds.insert(round_id);
+        self.internal_escrow(&token_id, deposit);
+
+        log_round_created_event(round_id, &token_id, start, end, deposit.into());
+
+        round_id
+    }
+
+    /// Add NEAR to a round's matching pool
+    #[payable]
+    pub fn fund_round(&mut self, round_id: RoundId) {
+        let amount = env::attached_deposit().as_yoctonear();
+        require!(amount > 0, "Requires attached deposit");
+        let round = self
+            .rounds
+            .get(&round_id)
+            .unwrap_or_else(|| env::panic_str("Round not found"));
+        require!(
+            round.token_id == near_token_id(),
+            "Round is not funded in NEAR"
+        );
+
+        self.internal_fund_round(round_id, &env::predecessor_account_id(), amount);
+    }
+
+    /// Pay out up to `limit` handles of an ended round as pending claims. Once every handle
+    /// is processed the round is finalized and any undistributed pool returns to the sponsor
+    pub fn finalize_round(&mut self, round_id: RoundId, limit: Option<u64>) {
+        let mut round = self
+            .rounds
+            .get(&round_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Round not found"));
+        require!(!round.finalized, "Round already finalized");
+        require!(env::block_timestamp() >= round.end, "Round has not ended");
+
+        let limit = limit
+            .unwrap_or(MAX_HANDLES_PER_FINALIZE)
+            .min(MAX_HANDLES_PER_FINALIZE);
+        let handles: Vec<SocialHandle> = self
+            .round_handles
+            .get(&round_id)
+            .map(|handles| {
+                handles
+                    .iter()
+                    .skip(round.finalize_cursor as usize)
+                    .take(limit as usize)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let total_handles = self
+            .round_handles
+            .get(&round_id)
+            .map_or(0, |handles| handles.len() as u64);
+
+        for social_handle in handles {
+            let score = self
+                .round_matches
+                .get(&(round_id, social_handle.clone()))
+                .map_or(0, |handle_match| handle_match.score());
+            let amount = round.match_for(score);
+            round.finalize_cursor += 1;
+            if amount == 0 {
+                continue;
+            }
+
+            round.distributed = U128(round.distributed.0 + amount);
+            // The match moves from the round pool to the handle's claim
+            self.internal_release(&round.token_id, amount);
+            let mut claim = if round.token_id == near_token_id() {
+                Claim::new_near(round.sponsor.clone(), amount, social_handle.to_string())
+            } else {
+                Claim::new_ft(
+                    round.sponsor.clone(),
+                    round.token_id.clone(),
+                    amount,
+                    social_handle.to_string(),
+                )
+            };
+            claim.expires_at = self.internal_expires_at(&round.token_id, None);
+            self.store_claim(social_handle, claim);
+        }
+
+        if round.finalize_cursor >= total_handles {
+            round.finalized = true;
+            self.active_rounds.remove(&round_id);
+
+            let leftover = round.matching_pool.0 - round.distributed.0;
+            if leftover > 0 {
+                self.internal_release(&round.token_id, leftover);
+                if round.token_id == near_token_id() {
+                    Promise::new(round.sponsor.clone())
+                        .transfer(NearToken::from_yoctonear(leftover));
+                } else {
+                    external::ext_ft::ext(round.token_id.clone())
+                        .with_attached_deposit(NearToken::from_yoctonear(1))
+                        .with_static_gas(GAS_FOR_FT_TRANSFER)
+                        .ft_transfer(
+                            round.sponsor.clone(),
+                            leftover.to_string(),
+                            Some(format!("Undistributed matching pool of round {}", round_id)),
+                        );
+                }
+            }
+
+            log_round_finalized_event(round_id, round.distributed, leftover.into());
+        }
+
+        self.rounds.insert(round_id, round);
+    }
+
+    pub fn get_round(&self, round_id: RoundId) -> Option<RoundExternal> {
+        self.rounds
+            .get(&round_id)
+            .map(|round| self.format_round(round_id, round))
+    }
+
+    pub fn get_rounds(&self, cursor: Option<u64>, limit: u64) -> Page<RoundExternal> {
+        paginate(
+            self.rounds.iter(),
+            self.rounds.len() as u64,
+            cursor,
+            limit,
+            |(round_id, round)| Some(self.format_round(*round_id, round)),
+        )
+    }
+
+    /// Contributions to a handle in a round and its current share of the matching pool
+    pub fn get_round_match(
+        &self,
+        round_id: RoundId,
+        platform: String,
+        handle: String,
+    ) -> Option<(HandleMatch, U128)> {
+        let round = self.rounds.get(&round_id)?;
+        let handle_match = self
+            .round_matches
+            .get(&(round_id, SocialHandle::new(platform, handle)))?;
+        Some((
+            handle_match.clone(),
+            round.match_for(handle_match.score()).into(),
+        ))
+    }
+
+    fn format_round(&self, round_id: RoundId, round: &Round) -> RoundExternal {
+        RoundExternal {
+            id: round_id,
+            sponsor: round.sponsor.clone(),
+            token_id: round.token_id.clone(),
+            start: round.start,
+            end: round.end,
+            matching_pool: round.matching_pool,
+            total_score: round.total_score,
+            distributed: round.distributed,
+            handles: self
+                .round_handles
+                .get(&round_id)
+                .map_or(0, |handles| handles.len() as u64),
+            finalized: round.finalized,
+        }
+    }
+
+    pub(crate) fn internal_fund_round(
+        &mut self,
+        round_id: RoundId,
+        funder: &AccountId,
+        amount: u128,
+    ) {
+        let round = self
+            .rounds
+            .get_mut(&round_id)
+            .unwrap_or_else(|| env::panic_str("Round not found"));
+        require!(
+            env::block_timestamp() < round.end,
+            "Round has already ended"
+        );
+        round.matching_pool = U128(round.matching_pool.0 + amount);
+        let token_id = round.token_id.clone();
+        self.internal_escrow(&token_id, amount);
+
+        log_round_funded_event(round_id, funder, amount.into());
+    }
+
+    // Internal helper to count a tip towards every active round matching its token,
+    // returning the rounds it was counted in
+    pub(crate) fn internal_record_round_contribution(
+        &mut self,
+        social_handle: &SocialHandle,
+        tipper: &AccountId,
+        token_id: &AccountId,
+        amount: u128,
+    ) -> Vec<RoundId> {
+        let round_ids: Vec<RoundId> = self
+            .active_rounds
+            .iter()
+            .filter(|round_id| {
+                self.rounds
+                    .get(round_id)
+                    .is_some_and(|round| round.is_active() && &round.token_id == token_id)
+            })
+            .cloned()
+            .collect();
+        for round_id in round_ids.iter() {
+            let contribution_key = (*round_id, social_handle.clone(), tipper.clone());
+            let previous = self
+                .round_contributions
+                .get(&contribution_key)
+                .copied()
+                .unwrap_or(0);
+            self.internal_update_contribution(contribution_key, previous, previous + amount);
+            self.round_handles
+                .entry(*round_id)
+                .or_insert_with(|| {
+                    IterableSet::new(StorageKey::HandlesByRound {
+                        round_id: *round_id,
+                    })
+                })
+                .insert(social_handle.clone());
+        }
+        round_ids
+    }
+
+    // Internal helper to take a tip that went back to its tipper out of the rounds it was
+    // counted in. Rounds already finalized have paid out their matches and are left as is
+    pub(crate) fn internal_reverse_round_contribution(&mut self, claim: &Claim) {
+        for round_id in claim.rounds.iter() {
+            if self
+                .rounds
+                .get(round_id)
+                .is_none_or(|round| round.finalized)
+            {
+                continue;
+            }
+            let contribution_key = (*round_id, claim.social_handle(), claim.tipper().clone());
+            let previous = self
+                .round_contributions
+                .get(&contribution_key)
+                .copied()
+                .unwrap_or(0);
+            let current = previous.saturating_sub(claim.amount());
+            self.internal_update_contribution(contribution_key, previous, current);
+        }
+    }
+
+    // Moves a tipper's contribution to a handle from `previous` to `current`, updating the
+    // handle's score and the round's total score
+    fn internal_update_contribution(
+        &mut self,
+        contribution_key: (RoundId, SocialHandle, AccountId),
+        previous: u128,
+        current: u128,
+    ) {
+        let (round_id, social_handle, _) = contribution_key.clone();
+        if current == 0 {
+            self.round_contributions.remove(&contribution_key);
+        } else {
+            self.round_contributions.insert(contribution_key, current);
+        }
+
+        let handle_match = self
+            .round_matches
+            .entry((round_id, social_handle))
+            .or_default();
+        let previous_score = handle_match.score();
+        if previous == 0 && current > 0 {
+            handle_match.contributors += 1;
+        } else if previous > 0 && current == 0 {
+            handle_match.contributors -= 1;
+        }
+        handle_match.total = U128(handle_match.total.0 + current - previous);
+        handle_match.sqrt_sum = U128(handle_match.sqrt_sum.0 + isqrt(current) - isqrt(previous));
+        let score = handle_match.score();
+
+        if let Some(round) = self.rounds.get_mut(&round_id) {
+            round.total_score = U128(
+                round
+                    .total_score
+                    .0
+                    .saturating_sub(previous_score)
+                    .saturating_add(score),
+            );
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::*;
+
+/// Where a creator's incoming tips are donated instead of paid to their wallet
+#[near(serializers=[borsh, json])]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TipRoute {
+    /// Donate to the creator's project in a PotLock pot. NEAR only, FTs are paid directly
+    Pot { pot_id: AccountId },
+    /// Donate to the creator through a PotLock donation contract
+    DonationContract { contract_id: AccountId },
+}
+
+impl TipRoute {
+    pub fn contract_id(&self) -> &AccountId {
+        match self {
+            TipRoute::Pot { pot_id } => pot_id,
+            TipRoute::DonationContract { contract_id } => contract_id,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Route a handle's tips into a PotLock pot or donation contract, or clear the route.
+    /// Only the linked account can change it
+    pub fn set_tip_route(&mut self, platform: String, handle: String, route: Option<TipRoute>) {
+        let social_handle = SocialHandle::new(platform, handle);
+        require!(
+            self.linked_account(&social_handle) == Some(&env::predecessor_account_id()),
+            "Only the linked account can route tips"
+        );
+
+        match &route {
+            Some(route) => {
+                self.tip_routes
+                    .insert(social_handle.to_string(), route.clone());
+            }
+            None => {
+                self.tip_routes.remove(&social_handle.to_string());
+            }
+        }
+
+        log_tip_route_updated_event(&social_handle.platform, &social_handle.handle, &route);
+    }
+
+    pub fn get_tip_route(&self, platform: String, handle: String) -> Option<TipRoute> {
+        let social_handle = SocialHandle::new(platform, handle);
+        self.tip_routes.get(&social_handle.to_string()).cloned()
+    }
+
+    /// Settles a direct tip that was donated on the creator's behalf.
+    /// Whatever didn't reach the donation contract is kept as a pending claim
+    #[private]
+    pub fn on_tip_forwarded(&mut self, social_handle: SocialHandle, claim: Claim) {
+        let forwarded = forwarded_amount(&claim);
+        let mut claim = claim;
+        if forwarded < claim.amount() {
+            claim.amount = NearToken::from_yoctonear(claim.amount() - forwarded);
+            env::log_str(&format!(
+                "Forwarding tip for {} failed, stored as pending claim",
+                social_handle
+            ));
+            self.store_claim(social_handle, claim);
+        }
+    }
+
+    /// Settles a claimed balance that was donated on the creator's behalf. A failed
+    /// donation is queued for `retry_settlement`, whatever the donation contract didn't
+    /// keep stays pending on the claim
+    #[private]
+    pub fn on_claim_forwarded(
+        &mut self,
+        social_handle: SocialHandle,
+        claim_id: ClaimId,
+        recipient: AccountId,
+    ) {
+        if let Some(mut claim) = self.claims_by_id.get(&claim_id).cloned() {
+            let forwarded = forwarded_amount(&claim);
+            if forwarded == 0 {
+                env::log_str(&format!(
+                    "Forwarding claim {} for {} failed",
+                    claim_id, social_handle
+                ));
+                self.internal_settlement_failed(
+                    claim_id,
+                    format!("Donation through the tip route of {} failed", social_handle),
+                );
+                return;
+            }
+            let Some(settlement) = self.settlements.remove(&claim_id) else {
+                return;
+            };
+            claim.status = settlement.previous_status;
+
+            self.internal_release_claim(&claim, forwarded);
+            self.internal_record_settlement_stats(&claim, forwarded, false);
+            let remaining = claim.amount() - forwarded;
+            if remaining == 0 {
+                claim.status = ClaimStatus::Claimed;
+            } else {
+                claim.amount = NearToken::from_yoctonear(remaining);
+            }
+            log_claim_processed_event(
+                &social_handle.platform,
+                &social_handle.handle,
+                forwarded.into(),
+                claim.token_type(),
+                &recipient,
+            );
+            self.claims_by_id.insert(claim_id, claim);
+        }
+    }
+
+    // Settles a claim's donation in `on_claim_forwarded`
+    pub(crate) fn internal_settle_forward(
+        &self,
+        forward: Promise,
+        social_handle: SocialHandle,
+        claim_id: ClaimId,
+        recipient: AccountId,
+    ) -> Promise {
+        forward.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_SETTLEMENT_CALLBACK)
+                .on_claim_forwarded(social_handle, claim_id, recipient),
+        )
+    }
+
+    /// Donation promise for a handle's route, or None when the route can't take this asset
+    pub(crate) fn internal_forward(
+        &self,
+        social_handle: &SocialHandle,
+        claim: &Claim,
+        recipient: &AccountId,
+        message: String,
+    ) -> Option<Promise> {
+        let route = self.tip_routes.get(&social_handle.to_string())?;
+        match (route, &claim.claim_type) {
+            (TipRoute::Pot { pot_id }, ClaimType::Near) => Some(
+                external::ext_pot::ext(pot_id.clone())
+                    .with_attached_deposit(claim.amount)
+                    .with_static_gas(GAS_FOR_DONATION)
+                    .donate(
+                        Some(message),
+                        None,
+                        Some(recipient.clone()),
+                        None,
+                        None,
+                        None,
+                    ),
+            ),
+            (TipRoute::DonationContract { contract_id }, ClaimType::Near) => Some(
+                external::ext_donation::ext(contract_id.clone())
+                    .with_attached_deposit(claim.amount)
+                    .with_static_gas(GAS_FOR_DONATION)
+                    .donate(recipient.clone(), Some(message), None, None),
+            ),
+            (
+                TipRoute::DonationContract { contract_id },
+                ClaimType::FungibleToken {
+                    contract_id: token_id,
+                },
+            ) => Some(
+                external::ext_ft::ext(token_id.clone())
+                    .with_attached_deposit(NearToken::from_yoctonear(1))
+                    .with_static_gas(GAS_FOR_DONATION)
+                    .ft_transfer_call(
+                        contract_id.clone(),
+                        claim.amount.as_yoctonear().to_string(),
+                        Some(message),
+                        serde_json::json!({ "recipient_id": recipient }).to_string(),
+                    ),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Amount that reached the donation contract, read from the forwarding promise result
+fn forwarded_amount(claim: &Claim) -> u128 {
+    match env::promise_result(0) {
+        PromiseResult::Successful(value) => match claim.claim_type {
+            // `ft_transfer_call` resolves to the amount the receiver kept
+            ClaimType::FungibleToken { .. } => serde_json::from_slice::<U128>(&value)
+                .map(|used| used.0.min(claim.amount()))
+                .unwrap_or(0),
+            _ => claim.amount(),
+        },
+        PromiseResult::Failed => 0,
+    }
+}
@@ -0,0 +1,242 @@
+use crate::*;
+
+/// Failed transfers allowed before a claim is returned to its previous status
+const MAX_SETTLEMENT_ATTEMPTS: u32 = 3;
+/// Time after which a transfer whose callback never resolved can be retried (1 day)
+const SETTLEMENT_LOCK_TIMEOUT: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// How a settlement pays out its claim
+#[near(serializers=[borsh, json])]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SettlementKind {
+    /// Transfer to the linked account
+    Claim,
+    /// Transfer back to the tipper
+    Reclaim,
+    /// Transfer back to the tipper of a cancelled tip
+    Cancel,
+    /// Donation through the handle's tip route
+    Forward,
+    /// Sweep of an expired claim, less the incentive owed to the caller.
+    /// `pot` donates to the beneficiary's matching pool instead of transferring
+    Sweep {
+        pot: bool,
+        caller: AccountId,
+        incentive: U128,
+    },
+}
+
+/// A claim transfer in flight or waiting for a retry
+#[near(serializers=[borsh])]
+#[derive(Clone)]
+pub struct Settlement {
+    pub beneficiary: AccountId,
+    pub kind: SettlementKind,
+    /// Status the claim returns to once all attempts have failed
+    pub previous_status: ClaimStatus,
+    pub failed_attempts: u32,
+    /// Set while waiting for `retry_settlement`, cleared while a transfer is in flight
+    pub failure_reason: Option<String>,
+    pub failed_at: Option<u64>,
+    /// When the last transfer was sent
+    pub locked_at: u64,
+}
+
+/// Settlement view. `beneficiary` is None when it's the tipper of an anonymous claim
+#[near(serializers=[json])]
+pub struct SettlementExternal {
+    pub claim_id: ClaimId,
+    pub beneficiary: Option<AccountId>,
+    pub kind: SettlementKind,
+    pub previous_status: ClaimStatus,
+    pub failed_attempts: u32,
+    pub failure_reason: Option<String>,
+    pub failed_at: Option<u64>,
+    pub locked_at: u64,
+}
+
+/// Account a claim is settled to as shown publicly, hiding the tipper of an anonymous claim
+pub(crate) fn public_beneficiary(claim: &Claim, beneficiary: &AccountId) -> String {
+    if beneficiary == claim.tipper() {
+        claim.public_tipper()
+    } else {
+        beneficiary.to_string()
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Retry a failed claim transfer. The tipper can retry too, so a claim stuck on a
+    /// deleted beneficiary account eventually returns to its previous status. A transfer
+    /// whose callback never ran can be retried once `SETTLEMENT_LOCK_TIMEOUT` has passed
+    pub fn retry_settlement(&mut self, claim_id: ClaimId) -> Promise {
+        let settlement = self
+            .settlements
+            .get(&claim_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("No failed settlement for this claim"));
+        require!(
+            settlement.failure_reason.is_some()
+                || env::block_timestamp()
+                    >= settlement.locked_at.saturating_add(SETTLEMENT_LOCK_TIMEOUT),
+            "Settlement is already in flight"
+        );
+        let claim = self
+            .claims_by_id
+            .get(&claim_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Claim not found"));
+        let caller = env::predecessor_account_id();
+        require!(
+            caller == settlement.beneficiary || &caller == claim.tipper(),
+            "Only the beneficiary or the tipper can retry a settlement"
+        );
+        self.assert_not_paused(match settlement.kind {
+            SettlementKind::Reclaim | SettlementKind::Cancel => Operation::Reclaim,
+            SettlementKind::Sweep { .. } => Operation::Sweep,
+            SettlementKind::Claim | SettlementKind::Forward => Operation::Claim,
+        });
+
+        if let Some(settlement) = self.settlements.get_mut(&claim_id) {
+            settlement.failure_reason = None;
+            settlement.locked_at = env::block_timestamp();
+        }
+        let social_handle = claim.social_handle();
+        let memo = "Retried settlement".to_string();
+        match settlement.kind {
+            SettlementKind::Claim | SettlementKind::Reclaim | SettlementKind::Cancel => self
+                .internal_send_settlement(
+                    social_handle,
+                    claim_id,
+                    &claim,
+                    settlement.beneficiary,
+                    memo,
+                    (settlement.kind != SettlementKind::Claim).then_some(true),
+                ),
+            SettlementKind::Forward => {
+                match self.internal_forward(
+                    &social_handle,
+                    &claim,
+                    &settlement.beneficiary,
+                    memo.clone(),
+                ) {
+                    Some(forward) => self.internal_settle_forward(
+                        forward,
+                        social_handle,
+                        claim_id,
+                        settlement.beneficiary,
+                    ),
+                    // The route was cleared since, pay the linked account directly
+                    None => {
+                        if let Some(settlement) = self.settlements.get_mut(&claim_id) {
+                            settlement.kind = SettlementKind::Claim;
+                        }
+                        self.internal_send_settlement(
+                            social_handle,
+                            claim_id,
+                            &claim,
+                            settlement.beneficiary,
+                            memo,
+                            None,
+                        )
+                    }
+                }
+            }
+            SettlementKind::Sweep { pot, incentive, .. } => {
+                self.internal_send_sweep(claim_id, &claim, settlement.beneficiary, pot, incentive)
+            }
+        }
+    }
+
+    pub fn get_settlement(&self, claim_id: ClaimId) -> Option<SettlementExternal> {
+        self.settlements
+            .get(&claim_id)
+            .map(|settlement| self.format_settlement(claim_id, settlement))
+    }
+
+    /// Settlements whose last transfer failed and that can be retried
+    pub fn get_failed_settlements(
+        &self,
+        cursor: Option<ClaimId>,
+        limit: u64,
+    ) -> Page<SettlementExternal, ClaimId> {
+        paginate_keys(&self.settlements, cursor, limit, |claim_id, settlement| {
+            settlement
+                .failure_reason
+                .is_some()
+                .then(|| self.format_settlement(*claim_id, settlement))
+        })
+    }
+
+    fn format_settlement(&self, claim_id: ClaimId, settlement: &Settlement) -> SettlementExternal {
+        let hidden = self
+            .claims_by_id
+            .get(&claim_id)
+            .is_some_and(|claim| claim.is_anonymous() && &settlement.beneficiary == claim.tipper());
+        SettlementExternal {
+            claim_id,
+            beneficiary: (!hidden).then(|| settlement.beneficiary.clone()),
+            kind: settlement.kind.clone(),
+            previous_status: settlement.previous_status,
+            failed_attempts: settlement.failed_attempts,
+            failure_reason: settlement.failure_reason.clone(),
+            failed_at: settlement.failed_at,
+            locked_at: settlement.locked_at,
+        }
+    }
+
+    // Records a settlement and marks the claim `Settling` until its transfer resolves
+    pub(crate) fn internal_lock_claim(
+        &mut self,
+        claim_id: ClaimId,
+        claim: &Claim,
+        beneficiary: AccountId,
+        kind: SettlementKind,
+    ) {
+        self.settlements.insert(
+            claim_id,
+            Settlement {
+                beneficiary,
+                kind,
+                previous_status: claim.status,
+                failed_attempts: 0,
+                failure_reason: None,
+                failed_at: None,
+                locked_at: env::block_timestamp(),
+            },
+        );
+        if let Some(stored) = self.claims_by_id.get_mut(&claim_id) {
+            stored.status = ClaimStatus::Settling;
+        }
+    }
+
+    // Queues a failed transfer for retry, or restores the claim once attempts run out
+    pub(crate) fn internal_settlement_failed(&mut self, claim_id: ClaimId, reason: String) {
+        let Some(settlement) = self.settlements.get_mut(&claim_id) else {
+            return;
+        };
+        settlement.failed_attempts += 1;
+        let failed_attempts = settlement.failed_attempts;
+
+        let beneficiary = settlement.beneficiary.clone();
+        if failed_attempts >= MAX_SETTLEMENT_ATTEMPTS {
+            let settlement = self.settlements.remove(&claim_id).unwrap();
+            let claim = self.claims_by_id.get_mut(&claim_id);
+            let beneficiary = claim.map_or(beneficiary.to_string(), |claim| {
+                claim.status = settlement.previous_status;
+                public_beneficiary(claim, &beneficiary)
+            });
+            log_settlement_abandoned_event(claim_id, &beneficiary, &reason);
+        } else {
+            settlement.failure_reason = Some(reason.clone());
+            settlement.failed_at = Some(env::block_timestamp());
+            let beneficiary = self
+                .claims_by_id
+                .get(&claim_id)
+                .map_or(beneficiary.to_string(), |claim| {
+                    public_beneficiary(claim, &beneficiary)
+                });
+            log_settlement_failed_event(claim_id, &beneficiary, &reason, failed_attempts);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::*;
+
+/// Running totals in one token
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Default)]
+pub struct TokenStats {
+    pub tips: u64,
+    /// Tipped to a handle, or given by a tipper
+    pub total: U128,
+    /// Paid out of pending claims to the handle
+    pub claimed: U128,
+    /// Returned to tippers
+    pub reclaimed: U128,
+}
+
+/// Aggregates for a handle or a tipper. Token totals are stored separately
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Default)]
+pub struct TipStats {
+    pub tip_count: u64,
+    /// Unique tippers for a handle, unique handles tipped for a tipper
+    pub counterparties: u64,
+    pub tokens: Vec<AccountId>,
+}
+
+#[near(serializers=[json])]
+pub struct TokenStatsExternal {
+    pub token_id: AccountId,
+    pub stats: TokenStats,
+}
+
+#[near(serializers=[json])]
+pub struct TipStatsExternal {
+    pub tip_count: u64,
+    pub counterparties: u64,
+    /// Sorted by total, largest first
+    pub tokens: Vec<TokenStatsExternal>,
+}
+
+/// Whose aggregates a stats entry belongs to
+#[near(serializers=[borsh])]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatsKey {
+    Handle(SocialHandle),
+    Tipper(AccountId),
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Tips received by a handle, with a breakdown by token
+    pub fn get_handle_stats(&self, platform: String, handle: String) -> Option<TipStatsExternal> {
+        self.format_stats(&StatsKey::Handle(SocialHandle::new(platform, handle)))
+    }
+
+    /// Tips given by an account, anonymous tips excluded
+    pub fn get_tipper_stats(&self, account_id: AccountId) -> Option<TipStatsExternal> {
+        self.format_stats(&StatsKey::Tipper(account_id))
+    }
+
+    fn format_stats(&self, key: &StatsKey) -> Option<TipStatsExternal> {
+        let stats = self.tip_stats.get(key)?;
+        let mut tokens: Vec<TokenStatsExternal> = stats
+            .tokens
+            .iter()
+            .map(|token_id| TokenStatsExternal {
+                token_id: token_id.clone(),
+                stats: self
+                    .token_stats
+                    .get(&(key.clone(), token_id.clone()))
+                    .cloned()
+                    .unwrap_or_default(),
+            })
+            .collect();
+        tokens.sort_by_key(|token| std::cmp::Reverse(token.stats.total.0));
+
+        Some(TipStatsExternal {
+            tip_count: stats.tip_count,
+            counterparties: stats.counterparties,
+            tokens,
+        })
+    }
+
+    // Internal helper to count a tip, direct or pending, towards handle and tipper stats.
+    // Anonymous tips count towards the handle's tippers but not the tipper's handles
+    pub(crate) fn internal_record_tip_stats(
+        &mut self,
+        social_handle: &SocialHandle,
+        tipper: &AccountId,
+        token_id: &AccountId,
+        amount: u128,
+        anonymous: bool,
+    ) {
+        let mut keys = vec![StatsKey::Handle(social_handle.clone())];
+        if !anonymous {
+            keys.push(StatsKey::Tipper(tipper.clone()));
+        }
+        for key in keys {
+            let first_tip = self
+                .tipped_pairs
+                .insert((key.clone(), counterparty(&key, social_handle, tipper)));
+            let stats = self.tip_stats.entry(key.clone()).or_default();
+            stats.tip_count += 1;
+            if first_tip {
+                stats.counterparties += 1;
+            }
+            if !stats.tokens.contains(token_id) {
+                stats.tokens.push(token_id.clone());
+            }
+
+            let token_stats = self
+                .token_stats
+                .entry((key.clone(), token_id.clone()))
+                .or_default();
+            token_stats.tips += 1;
+            token_stats.total = U128(token_stats.total.0 + amount);
+            let ranked = token_stats.total.0.saturating_sub(token_stats.reclaimed.0);
+
+            let (leaderboard_key, id) = leaderboard_entry(&key, token_id);
+            self.internal_update_leaderboard(leaderboard_key, id, ranked);
+        }
+    }
+
+    // Internal helper to count a settled claim as claimed by the handle or reclaimed by the tipper
+    pub(crate) fn internal_record_settlement_stats(
+        &mut self,
+        claim: &Claim,
+        amount: u128,
+        reclaimed: bool,
+    ) {
+        let token_id = claim.claim_type.token_id();
+        let mut keys = vec![StatsKey::Handle(claim.social_handle())];
+        if !claim.is_anonymous() {
+            keys.push(StatsKey::Tipper(claim.tipper().clone()));
+        }
+        for key in keys {
+            let token_stats = self
+                .token_stats
+                .entry((key.clone(), token_id.clone()))
+                .or_default();
+            if reclaimed {
+                token_stats.reclaimed = U128(token_stats.reclaimed.0 + amount);
+                // Returned tips no longer count towards the ranking
+                let ranked = token_stats.total.0.saturating_sub(token_stats.reclaimed.0);
+                let (leaderboard_key, id) = leaderboard_entry(&key, &token_id);
+                self.internal_update_leaderboard(leaderboard_key, id, ranked);
+            } else {
+                token_stats.claimed = U128(token_stats.claimed.0 + amount);
+            }
+        }
+    }
+}
+
+// The other side of a tip from the point of view of a stats entry
+fn counterparty(key: &StatsKey, social_handle: &SocialHandle, tipper: &AccountId) -> String {
+    match key {
+        StatsKey::Handle(_) => tipper.to_string(),
+        StatsKey::Tipper(_) => social_handle.to_string(),
+    }
+}
+
+// Leaderboard and entry id a stats entry is ranked under
+fn leaderboard_entry(key: &StatsKey, token_id: &AccountId) -> (LeaderboardKey, String) {
+    match key {
+        StatsKey::Handle(social_handle) => (
+            LeaderboardKey::Handles {
+                platform: social_handle.platform.clone(),
+                token_id: token_id.clone(),
+            },
+            social_handle.to_string(),
+        ),
+        StatsKey::Tipper(account_id) => (
+            LeaderboardKey::Tippers {
+                token_id: token_id.clone(),
+            },
+            account_id.to_string(),
+        ),
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+/// Storage charged to the tipper for each tip held as a pending claim: the claim and its indexes
+pub const TIP_STORAGE_COST: NearToken = NearToken::from_millinear(10);
+
+#[near_bindgen]
+impl Contract {
+    /// Deposit NEAR to cover the storage of `account_id`'s tips, the caller by default.
+    /// Every pending tip draws `TIP_STORAGE_COST` from the tipper's balance. NEAR tips fall
+    /// back to the attached deposit, so only FT and NFT tips to unlinked handles need a balance
+    #[payable]
+    pub fn storage_deposit(&mut self, account_id: Option<AccountId>) -> U128 {
+        let amount = env::attached_deposit().as_yoctonear();
+        require!(amount > 0, "Requires attached deposit");
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+
+        let balance = self.storage_balances.entry(account_id).or_insert(0);
+        *balance += amount;
+        let balance = *balance;
+        // Held for the tipper until it's spent on tips or withdrawn
+        self.internal_escrow(&near_token_id(), amount);
+
+        balance.into()
+    }
+
+    /// Withdraw unspent storage balance, all of it when `amount` is omitted
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> Promise {
+        require!(
+            env::attached_deposit() == NearToken::from_yoctonear(1),
+            "Requires attached deposit of exactly 1 yoctoNEAR"
+        );
+        let account_id = env::predecessor_account_id();
+        let balance = self.storage_balances.get(&account_id).copied().unwrap_or(0);
+        let amount = amount.map_or(balance, |amount| amount.0);
+        require!(
+            amount > 0 && amount <= balance,
+            "Amount exceeds storage balance"
+        );
+
+        if amount == balance {
+            self.storage_balances.remove(&account_id);
+        } else {
+            self.storage_balances
+                .insert(account_id.clone(), balance - amount);
+        }
+        self.internal_release(&near_token_id(), amount);
+        Promise::new(account_id).transfer(NearToken::from_yoctonear(amount))
+    }
+
+    pub fn storage_balance_of(&self, account_id: AccountId) -> U128 {
+        self.storage_balances
+            .get(&account_id)
+            .copied()
+            .unwrap_or(0)
+            .into()
+    }
+
+    // Internal helper to pay for a pending tip's storage from its tipper's balance or, for NEAR
+    // tips, out of the `attached` amount. Returns what is left of `attached` for the tip
+    pub(crate) fn internal_charge_tip_storage(
+        &mut self,
+        tipper: &AccountId,
+        attached: Option<u128>,
+    ) -> u128 {
+        let cost = TIP_STORAGE_COST.as_yoctonear();
+        let balance = self.storage_balances.get(tipper).copied().unwrap_or(0);
+        if balance >= cost {
+            self.storage_balances.insert(tipper.clone(), balance - cost);
+            // The contract keeps the cost to pay for the tip's storage
+            self.internal_release(&near_token_id(), cost);
+            return attached.unwrap_or(0);
+        }
+
+        match attached {
+            Some(attached) => {
+                require!(
+                    attached > cost,
+                    format!(
+                        "Tip must exceed the storage cost of {}",
+                        TIP_STORAGE_COST.exact_amount_display()
+                    )
+                );
+                attached - cost
+            }
+            None => env::panic_str(&format!(
+                "Insufficient storage balance, deposit at least {} with storage_deposit",
+                TIP_STORAGE_COST.exact_amount_display()
+            )),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::*;
+
+/// Maximum claims swept per `sweep_expired` call
+const MAX_SWEEPS_PER_CALL: u64 = 5;
+/// Maximum claims scanned per `sweep_expired` call
+const MAX_SWEEP_SCAN: usize = 200;
+/// Upper bound of the caller incentive, in basis points
+pub(crate) const MAX_SWEEP_INCENTIVE_BPS: u16 = 500;
+/// Shortest grace period tippers get to reclaim before a claim can be swept (7 days)
+const MIN_SWEEP_GRACE_PERIOD: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+const DEFAULT_SWEEP_GRACE_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+const DEFAULT_SWEEP_INCENTIVE_BPS: u16 = 10;
+
+/// Where expired claims go once the grace period has passed
+#[near(serializers=[borsh, json])]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SweepDestination {
+    /// Return the funds to the tipper
+    Tipper,
+    Treasury {
+        account_id: AccountId,
+    },
+    /// Donate to a PotLock pot's matching pool. NEAR only, other assets return to the tipper
+    Pot {
+        pot_id: AccountId,
+    },
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct SweepConfig {
+    /// Time after expiry the tipper has to reclaim before the claim can be swept
+    pub grace_period: u64,
+    pub destination: SweepDestination,
+    /// Share of each swept NEAR or FT amount paid to the caller, in basis points
+    pub incentive_bps: u16,
+}
+
+impl Default for SweepConfig {
+    fn default() -> Self {
+        Self {
+            grace_period: DEFAULT_SWEEP_GRACE_PERIOD,
+            destination: SweepDestination::Tipper,
+            incentive_bps: DEFAULT_SWEEP_INCENTIVE_BPS,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Settle up to `limit` claims that stayed unreclaimed past the grace period, scanning
+    /// from where the previous call stopped. The caller earns `incentive_bps` of each amount
+    pub fn sweep_expired(&mut self, limit: Option<u64>) -> u64 {
+        self.assert_not_paused(Operation::Sweep);
+
+        let total = self.claims_by_id.len() as u64;
+        if self.sweep_cursor >= total {
+            self.sweep_cursor = 0;
+        }
+        let limit = limit
+            .unwrap_or(MAX_SWEEPS_PER_CALL)
+            .min(MAX_SWEEPS_PER_CALL) as usize;
+        let now = env::block_timestamp();
+        let grace_period = self.sweep_config.grace_period;
+
+        let mut scanned = 0;
+        let mut sweepable = Vec::new();
+        for (claim_id, claim) in self
+            .claims_by_id
+            .iter()
+            .skip(self.sweep_cursor as usize)
+            .take(MAX_SWEEP_SCAN)
+        {
+            if sweepable.len() == limit {
+                break;
+            }
+            scanned += 1;
+            if claim.is_reclaimable() && now >= claim.expires_at.saturating_add(grace_period) {
+                sweepable.push((*claim_id, claim.clone()));
+            }
+        }
+        self.sweep_cursor += scanned;
+
+        let swept = sweepable.len() as u64;
+        for (claim_id, claim) in sweepable {
+            self.internal_sweep_claim(claim_id, claim);
+        }
+        swept
+    }
+
+    /// Settles a swept claim, queueing a failed transfer for `retry_settlement`
+    #[private]
+    pub fn on_claim_swept(
+        &mut self,
+        claim_id: ClaimId,
+        #[callback_result] transfer_result: Result<(), PromiseError>,
+    ) {
+        if transfer_result.is_err() {
+            env::log_str(&format!("Sweeping claim {} failed", claim_id));
+            self.internal_settlement_failed(
+                claim_id,
+                format!("Sweep transfer for claim {} failed", claim_id),
+            );
+            return;
+        }
+        let Some(settlement) = self.settlements.remove(&claim_id) else {
+            return;
+        };
+        let SettlementKind::Sweep {
+            caller, incentive, ..
+        } = settlement.kind
+        else {
+            return;
+        };
+        let Some(mut claim) = self.claims_by_id.get(&claim_id).cloned() else {
+            return;
+        };
+        let destination = settlement.beneficiary;
+        // Funds that went back to the tipper count as reclaimed
+        claim.status = if &destination == claim.tipper() {
+            ClaimStatus::Reclaimed
+        } else {
+            ClaimStatus::Swept
+        };
+        self.claims_by_id.insert(claim_id, claim.clone());
+
+        self.internal_release_claim(&claim, claim.amount());
+        self.internal_record_settlement_stats(&claim, claim.amount(), true);
+        // The creator never received a swept tip, so it isn't matched either
+        self.internal_reverse_round_contribution(&claim);
+        if incentive.0 > 0 {
+            claim.claim_type.transfer(
+                caller.clone(),
+                NearToken::from_yoctonear(incentive.0),
+                "Sweep incentive".to_string(),
+            );
+        }
+
+        log_claim_swept_event(
+            claim_id,
+            (claim.amount() - incentive.0).into(),
+            claim.token_type(),
+            &public_beneficiary(&claim, &destination),
+            &caller,
+            incentive,
+        );
+    }
+
+    /// Set the grace period after expiry before claims can be swept (fee manager only).
+    /// The destination and caller incentive change through the admin timelock
+    pub fn set_sweep_grace_period(&mut self, grace_period: u64) {
+        self.assert_role(Role::FeeManager);
+        require!(
+            grace_period >= MIN_SWEEP_GRACE_PERIOD,
+            "Sweep grace period is too short"
+        );
+        self.sweep_config.grace_period = grace_period;
+
+        env::log_str(&format!("Sweep grace period changed to {}", grace_period));
+    }
+
+    pub fn get_sweep_config(&self) -> SweepConfig {
+        self.sweep_config.clone()
+    }
+
+    // Locks the claim as `Settling` so it can't be swept or reclaimed twice while the
+    // transfer is in flight
+    fn internal_sweep_claim(&mut self, claim_id: ClaimId, claim: Claim) {
+        let incentive = match claim.claim_type {
+            ClaimType::NonFungibleToken { .. } => 0,
+            _ => claim.amount() * self.sweep_config.incentive_bps as u128 / 10_000,
+        };
+        let (destination, pot) = match (&self.sweep_config.destination, &claim.claim_type) {
+            (SweepDestination::Treasury { account_id }, _) => (account_id.clone(), false),
+            (SweepDestination::Pot { pot_id }, ClaimType::Near) => (pot_id.clone(), true),
+            _ => (claim.tipper().clone(), false),
+        };
+
+        self.internal_lock_claim(
+            claim_id,
+            &claim,
+            destination.clone(),
+            SettlementKind::Sweep {
+                pot,
+                caller: env::predecessor_account_id(),
+                incentive: incentive.into(),
+            },
+        );
+        self.internal_send_sweep(claim_id, &claim, destination, pot, incentive.into());
+    }
+
+    // Internal helper to send a swept claim to its destination, settled in `on_claim_swept`
+    pub(crate) fn internal_send_sweep(
+        &self,
+        claim_id: ClaimId,
+        claim: &Claim,
+        destination: AccountId,
+        pot: bool,
+        incentive: U128,
+    ) -> Promise {
+        let amount = NearToken::from_yoctonear(claim.amount() - incentive.0);
+        let message = format!("Expired tip {} swept", claim_id);
+        let transfer = if pot {
+            external::ext_pot::ext(destination)
+                .with_attached_deposit(amount)
+                .with_static_gas(GAS_FOR_DONATION)
+                .donate(Some(message), None, None, Some(true), None, None)
+        } else {
+            claim.claim_type.transfer(destination, amount, message)
+        };
+
+        transfer.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_SETTLEMENT_CALLBACK)
+                .on_claim_swept(claim_id),
+        )
+    }
+}
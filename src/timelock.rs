@@ -0,0 +1,187 @@
+use crate::*;
+
+pub type ActionId = u64;
+
+/// An admin change that only takes effect after the timelock delay
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug)]
+pub enum AdminAction {
+    SetReclaimContract {
+        contract_id: AccountId,
+    },
+    RemoveToken {
+        token_id: AccountId,
+    },
+    SetAdminActionDelay {
+        delay: u64,
+    },
+    SetOwnershipTransferDelay {
+        delay: u64,
+    },
+    SetSweepDestination {
+        destination: SweepDestination,
+    },
+    SetSweepIncentive {
+        incentive_bps: u16,
+    },
+    /// Allows `upgrade` to deploy code with this sha256 hash
+    UpgradeContract {
+        code_hash: Base58CryptoHash,
+    },
+}
+
+impl AdminAction {
+    /// Role allowed to queue and execute the action, None for owner only
+    pub fn required_role(&self) -> Option<Role> {
+        match self {
+            AdminAction::SetReclaimContract { .. } => Some(Role::PlatformManager),
+            AdminAction::RemoveToken { .. } => Some(Role::TokenManager),
+            AdminAction::SetAdminActionDelay { .. } => None,
+            AdminAction::SetOwnershipTransferDelay { .. } => None,
+            AdminAction::SetSweepDestination { .. } | AdminAction::SetSweepIncentive { .. } => {
+                Some(Role::FeeManager)
+            }
+            AdminAction::UpgradeContract { .. } => Some(Role::Upgrader),
+        }
+    }
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct QueuedAction {
+    pub action: AdminAction,
+    pub proposer: AccountId,
+    pub queued_at: u64,
+    pub executable_at: u64,
+}
+
+#[near(serializers=[json])]
+pub struct QueuedActionExternal {
+    pub id: ActionId,
+    pub action: AdminAction,
+    pub proposer: AccountId,
+    pub queued_at: u64,
+    pub executable_at: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Queue an admin action. It becomes executable after `admin_action_delay`
+    pub fn queue_admin_action(&mut self, action: AdminAction) -> ActionId {
+        self.assert_action_role(&action);
+        if let AdminAction::SetSweepIncentive { incentive_bps } = &action {
+            require!(
+                *incentive_bps <= MAX_SWEEP_INCENTIVE_BPS,
+                "Sweep incentive is too high"
+            );
+        }
+
+        let now = env::block_timestamp();
+        let action_id = self.next_action_id;
+        self.next_action_id += 1;
+        let queued = QueuedAction {
+            action,
+            proposer: env::predecessor_account_id(),
+            queued_at: now,
+            executable_at: now + self.admin_action_delay,
+        };
+        log_admin_action_queued_event(action_id, &queued.action, queued.executable_at);
+        self.queued_actions.insert(action_id, queued);
+
+        action_id
+    }
+
+    /// Apply a queued action once its delay has passed
+    pub fn execute_admin_action(&mut self, action_id: ActionId) {
+        let queued = self
+            .queued_actions
+            .get(&action_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Action not found"));
+        self.assert_action_role(&queued.action);
+        require!(
+            !matches!(queued.action, AdminAction::UpgradeContract { .. }),
+            "Upgrades are executed with upgrade"
+        );
+        require!(
+            env::block_timestamp() >= queued.executable_at,
+            "Action is still timelocked"
+        );
+        self.queued_actions.remove(&action_id);
+
+        match &queued.action {
+            AdminAction::SetReclaimContract { contract_id } => {
+                self.reclaim_contract_id = contract_id.clone();
+            }
+            AdminAction::RemoveToken { token_id } => {
+                self.supported_tokens.remove(token_id);
+            }
+            AdminAction::SetAdminActionDelay { delay } => {
+                self.admin_action_delay = *delay;
+            }
+            AdminAction::SetOwnershipTransferDelay { delay } => {
+                self.ownership_transfer_delay = *delay;
+            }
+            AdminAction::SetSweepDestination { destination } => {
+                self.sweep_config.destination = destination.clone();
+            }
+            AdminAction::SetSweepIncentive { incentive_bps } => {
+                self.sweep_config.incentive_bps = *incentive_bps;
+            }
+            AdminAction::UpgradeContract { .. } => unreachable!(),
+        }
+
+        log_admin_action_executed_event(action_id, &queued.action);
+    }
+
+    /// Drop a queued action (owner or guardian)
+    pub fn cancel_admin_action(&mut self, action_id: ActionId) {
+        self.assert_role(Role::Guardian);
+        let queued = self
+            .queued_actions
+            .remove(&action_id)
+            .unwrap_or_else(|| env::panic_str("Action not found"));
+
+        log_admin_action_cancelled_event(action_id, &queued.action, &env::predecessor_account_id());
+    }
+
+    pub fn get_queued_action(&self, action_id: ActionId) -> Option<QueuedActionExternal> {
+        self.queued_actions
+            .get(&action_id)
+            .map(|queued| format_queued_action(action_id, queued))
+    }
+
+    pub fn get_queued_actions(
+        &self,
+        cursor: Option<ActionId>,
+        limit: u64,
+    ) -> Page<QueuedActionExternal, ActionId> {
+        paginate_keys(&self.queued_actions, cursor, limit, |action_id, queued| {
+            Some(format_queued_action(*action_id, queued))
+        })
+    }
+
+    pub fn get_admin_action_delay(&self) -> u64 {
+        self.admin_action_delay
+    }
+
+    fn assert_action_role(&self, action: &AdminAction) {
+        match action.required_role() {
+            Some(role) => self.assert_role(role),
+            None => require!(
+                env::predecessor_account_id() == self.owner_id,
+                "Only owner can perform this action"
+            ),
+        }
+    }
+}
+
+fn format_queued_action(action_id: ActionId, queued: &QueuedAction) -> QueuedActionExternal {
+    QueuedActionExternal {
+        id: action_id,
+        action: queued.action.clone(),
+        proposer: queued.proposer.clone(),
+        queued_at: queued.queued_at,
+        executable_at: queued.executable_at,
+    }
+}
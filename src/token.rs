@@ -2,6 +2,7 @@ use crate::*;
 
 #[near(serializers=[borsh, json])]
 #[derive(Clone, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum TokenStandard {
     NEAR,
     NEP141, // Fungible Token
@@ -15,6 +16,106 @@ pub struct TokenInfo {
     pub decimals: u8,
     pub symbol: String,
     pub chain: String, // "near" or "solana", is this needed tho?
+    /// New tips in this token are rejected while paused
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub expiry: ExpiryConfig,
+}
+
+/// Expiration rules for claims in a token. Periods are in nanoseconds
+#[near(serializers=[borsh, json])]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExpiryConfig {
+    /// Used when the tipper doesn't choose an expiry
+    pub default_period: u64,
+    pub min_period: u64,
+    pub max_period: u64,
+    /// Whether tippers can make claims that never expire
+    pub allow_never: bool,
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            default_period: CLAIM_EXPIRATION_PERIOD,
+            min_period: MIN_CLAIM_EXPIRATION_PERIOD,
+            max_period: MAX_CLAIM_EXPIRATION_PERIOD,
+            allow_never: false,
+        }
+    }
+}
+
+/// Key used for NEAR wherever tokens are keyed by account id
+pub fn near_token_id() -> AccountId {
+    "near".parse().unwrap()
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Set the expiry bounds for claims in a token, NEAR when omitted
+    pub fn set_token_expiry(&mut self, token_id: Option<AccountId>, expiry: ExpiryConfig) {
+        self.assert_role(Role::TokenManager);
+        require!(
+            expiry.min_period <= expiry.default_period
+                && expiry.default_period <= expiry.max_period,
+            "Default expiry must be within the min and max periods"
+        );
+
+        let token_id = token_id.unwrap_or_else(near_token_id);
+        if token_id == near_token_id() {
+            self.near_expiry = expiry.clone();
+        } else {
+            let token_info = self
+                .supported_tokens
+                .get_mut(&token_id)
+                .unwrap_or_else(|| env::panic_str("Unsupported token"));
+            token_info.expiry = expiry.clone();
+        }
+
+        log_token_expiry_updated_event(&token_id, &expiry, &env::predecessor_account_id());
+    }
+
+    /// Expiry bounds for claims in a token, NEAR when omitted
+    pub fn get_token_expiry(&self, token_id: Option<AccountId>) -> ExpiryConfig {
+        self.token_expiry(&token_id.unwrap_or_else(near_token_id))
+    }
+
+    /// Expiry of a new claim in a token, checking the tipper's choice against the token's bounds
+    pub(crate) fn internal_expires_at(
+        &self,
+        token_id: &AccountId,
+        expiry: Option<TipExpiry>,
+    ) -> u64 {
+        let config = self.token_expiry(token_id);
+        match expiry {
+            None => env::block_timestamp() + config.default_period,
+            Some(TipExpiry::Never) => {
+                require!(
+                    config.allow_never,
+                    "Token doesn't allow tips that never expire"
+                );
+                NEVER_EXPIRES
+            }
+            Some(TipExpiry::After(period)) => {
+                require!(
+                    config.min_period <= period && period <= config.max_period,
+                    "Expiry is outside the token's bounds"
+                );
+                env::block_timestamp() + period
+            }
+        }
+    }
+
+    pub(crate) fn token_expiry(&self, token_id: &AccountId) -> ExpiryConfig {
+        if *token_id == near_token_id() {
+            return self.near_expiry.clone();
+        }
+        self.supported_tokens
+            .get(token_id)
+            .map(|info| info.expiry.clone())
+            .unwrap_or_default()
+    }
 }
 
 // note on cross chain tipping, settlement will be done on near, which means that tipper can tip from solana, btc, eth, base, etc.
@@ -69,6 +69,14 @@ async fn test_claim_protocol() -> Result<(), Box<dyn std::error::Error>> {
         .json::<bool>()?;
     assert!(is_linked, "Account should be linked");
 
+    // Bob covers the storage of his tips up front
+    bob.call(contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?
+        .into_result()?;
+
     // **Test 2: Tipping with NEAR to a Linked Handle**
     let tip_amount = NearToken::from_near(1);
     let initial_alice_balance = alice.view_account().await?.balance;
@@ -212,18 +220,17 @@ async fn test_claim_protocol() -> Result<(), Box<dyn std::error::Error>> {
         .args_json(json!({
             "platform": "Twitter",
             "handle": unclaimed_handle,
-            "from_index": 0,
             "limit": 10
         }))
         .await?
-        .json::<Vec<serde_json::Value>>()?;
+        .json::<serde_json::Value>()?;
 
-    // Reclaim the first claim (index 0)
+    // Reclaim the first claim
     bob.call(contract.id(), "reclaim_tip")
         .args_json(json!({
             "platform": "Twitter",
             "handle": unclaimed_handle,
-            "claim_index": 0
+            "claim_id": claims["items"][0]["id"]
         }))
         .transact()
         .await?
@@ -238,20 +245,1842 @@ async fn test_claim_protocol() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-// Helper to create a mock proof structure
-fn create_mock_proof(handle: &str, platform: &str) -> serde_json::Value {
-    json!({
-        "claim_info": {
-            "provider": platform,
-            "parameters": handle,
-            "context": "test"
+// **Settlement: a forward that fails is queued and retried as a direct payout**
+#[tokio::test]
+async fn test_failed_forward_is_retried() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "carol789";
+
+    // Escrow a tip for an unlinked handle
+    tip_near(
+        &env.contract,
+        &env.bob,
+        handle,
+        NearToken::from_near(1),
+        json!(null),
+    )
+    .await?;
+    let claim_id = pending_claim_id(&env.contract, handle).await?;
+
+    // Carol links the handle and routes her tips to a donation contract that doesn't exist
+    link(&env.contract, &env.carol, handle, "3003").await?;
+    env.carol
+        .call(env.contract.id(), "set_tip_route")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "route": {"DonationContract": {"contract_id": "missing-donations.test.near"}}
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    env.carol
+        .call(env.contract.id(), "claim")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let settlement = env
+        .contract
+        .view("get_settlement")
+        .args_json(json!({"claim_id": claim_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(settlement["kind"], "Forward", "Forward should be recorded");
+    assert_eq!(settlement["failed_attempts"], 1);
+    assert!(
+        !settlement["failure_reason"].is_null(),
+        "Failed forward should wait for a retry"
+    );
+    assert_eq!(claim_status(&env.contract, claim_id).await?, "Settling");
+
+    // With the route cleared the retry pays the linked account directly
+    env.carol
+        .call(env.contract.id(), "set_tip_route")
+        .args_json(json!({"platform": "Twitter", "handle": handle, "route": null}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let initial_carol_balance = env.carol.view_account().await?.balance;
+    env.carol
+        .call(env.contract.id(), "retry_settlement")
+        .args_json(json!({"claim_id": claim_id}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let final_carol_balance = env.carol.view_account().await?.balance;
+    assert!(
+        final_carol_balance > initial_carol_balance,
+        "Carol should receive the retried claim"
+    );
+    assert_eq!(claim_status(&env.contract, claim_id).await?, "Claimed");
+
+    let settlement = env
+        .contract
+        .view("get_settlement")
+        .args_json(json!({"claim_id": claim_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(settlement.is_null(), "Settlement should be cleared");
+
+    Ok(())
+}
+
+// **Forwarding: a tip route doesn't survive the handle moving to another account**
+#[tokio::test]
+async fn test_relink_clears_tip_route() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "alice123";
+    link(&env.contract, &env.alice, handle, "1001").await?;
+    env.alice
+        .call(env.contract.id(), "set_tip_route")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "route": {"DonationContract": {"contract_id": "donations.test.near"}}
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    env.carol
+        .call(env.contract.id(), "dispute_link")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "proof": create_mock_proof_for_user(handle, "Twitter", "2002")
+        }))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    env.owner
+        .call(env.contract.id(), "resolve_dispute")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "move_link": true,
+            "slash_bond": false
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let route = env
+        .contract
+        .view("get_tip_route")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(route.is_null(), "Alice's route shouldn't apply to Carol");
+
+    Ok(())
+}
+
+// **Leaderboards: cancelled tips don't keep their place**
+#[tokio::test]
+async fn test_leaderboard_drops_cancelled_tips() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "bob_alt",
+        NearToken::from_near(2),
+        json!({"cancellable": true}),
+    )
+    .await?;
+    tip_near(
+        &env.contract,
+        &env.carol,
+        "alice123",
+        NearToken::from_near(1),
+        json!(null),
+    )
+    .await?;
+
+    let top_tippers = env
+        .contract
+        .view("get_top_tippers")
+        .args_json(json!({"limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(top_tippers["items"][0]["id"], env.bob.id().to_string());
+
+    let claim_id = pending_claim_id(&env.contract, "bob_alt").await?;
+    env.bob
+        .call(env.contract.id(), "cancel_tip")
+        .args_json(json!({"claim_id": claim_id}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let top_tippers = env
+        .contract
+        .view("get_top_tippers")
+        .args_json(json!({"limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(top_tippers["items"].as_array().map(Vec::len), Some(1));
+    assert_eq!(top_tippers["items"][0]["id"], env.carol.id().to_string());
+
+    let top_handles = env
+        .contract
+        .view("get_top_handles")
+        .args_json(json!({"platform": "Twitter", "limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(top_handles["items"][0]["id"], "twitter:alice123");
+    assert_eq!(top_handles["items"].as_array().map(Vec::len), Some(1));
+
+    Ok(())
+}
+
+// **Escrow: pending tips and storage balances are escrowed until they leave the contract**
+// **Stats: anonymous tips count for the handle only, storage comes out of the deposit**
+#[tokio::test]
+async fn test_stats_and_tip_storage() -> Result<(), Box<dyn std::error::Error>> {
+    let TestEnv {
+        contract, alice, ..
+    } = setup().await?;
+
+    // Alice has no storage balance, so her pending tip pays for its storage
+    alice
+        .call(contract.id(), "tip_near")
+        .args_json(json!({"platform": "Twitter", "handle": "dave", "anonymous": true}))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    let claims = contract
+        .view("get_pending_claims")
+        .args_json(json!({"platform": "Twitter", "handle": "dave", "limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    let storage_cost = NearToken::from_millinear(10).as_yoctonear();
+    assert_eq!(
+        claims["items"][0]["amount"],
+        json!((NearToken::from_near(1).as_yoctonear() - storage_cost).to_string())
+    );
+
+    // A later public tip still counts the handle for Alice
+    tip_near(
+        &contract,
+        &alice,
+        "dave",
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+    let tipper_stats = contract
+        .view("get_tipper_stats")
+        .args_json(json!({"account_id": alice.id()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(tipper_stats["tip_count"], json!(1));
+    assert_eq!(tipper_stats["counterparties"], json!(1));
+
+    let handle_stats = contract
+        .view("get_handle_stats")
+        .args_json(json!({"platform": "Twitter", "handle": "dave"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(handle_stats["tip_count"], json!(2));
+    assert_eq!(handle_stats["counterparties"], json!(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_escrow_follows_tips() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "escrow123";
+
+    let escrowed_before = escrowed_near(&env.contract).await?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        handle,
+        NearToken::from_near(1),
+        json!({"cancellable": true}),
+    )
+    .await?;
+
+    // The tip is escrowed and the storage cost leaves the tipper's storage balance
+    let storage_cost = NearToken::from_millinear(10).as_yoctonear();
+    let escrowed_after_tip = escrowed_near(&env.contract).await?;
+    assert_eq!(
+        escrowed_after_tip,
+        escrowed_before + NearToken::from_near(1).as_yoctonear() - storage_cost,
+        "Tip should be escrowed"
+    );
+
+    let claim_id = pending_claim_id(&env.contract, handle).await?;
+    env.bob
+        .call(env.contract.id(), "cancel_tip")
+        .args_json(json!({"claim_id": claim_id}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    assert_eq!(claim_status(&env.contract, claim_id).await?, "Reclaimed");
+    assert_eq!(
+        escrowed_near(&env.contract).await?,
+        escrowed_before - storage_cost,
+        "Cancelled tip should leave the escrow"
+    );
+
+    let report = env
+        .contract
+        .view("get_escrow_invariant")
+        .args_json(json!({}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(report["solvent"], true, "Contract should stay solvent");
+
+    Ok(())
+}
+
+// **Disputes: an arbiter can move a link to the challenger**
+#[tokio::test]
+async fn test_dispute_moves_link() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "alice123";
+    let alice_proof = create_mock_proof_for_user(handle, "Twitter", "1001");
+    link_with_proof(&env.contract, &env.alice, handle, alice_proof.clone()).await?;
+
+    // Replaying the proof the link was verified with can't open a dispute
+    let outcome = env
+        .carol
+        .call(env.contract.id(), "dispute_link")
+        .args_json(json!({"platform": "Twitter", "handle": handle, "proof": alice_proof}))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Replayed proof should be rejected");
+
+    // Carol's own proof must be generated after the link was verified
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    env.carol
+        .call(env.contract.id(), "dispute_link")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "proof": create_mock_proof_for_user(handle, "Twitter", "2002")
+        }))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let dispute = env
+        .contract
+        .view("get_dispute")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(dispute["challenger"], env.carol.id().to_string());
+
+    // The owner holds every role, arbiter included
+    env.owner
+        .call(env.contract.id(), "resolve_dispute")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "move_link": true,
+            "slash_bond": false
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let linked_account = env
+        .contract
+        .view("get_linked_account")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .await?
+        .json::<Option<AccountId>>()?;
+    assert_eq!(linked_account.as_ref(), Some(env.carol.id()));
+
+    Ok(())
+}
+
+// **Renames: linking a new handle for the same platform user retires the old one**
+#[tokio::test]
+async fn test_rename_moves_link() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    link(&env.contract, &env.alice, "alice123", "1001").await?;
+    link(&env.contract, &env.alice, "alice_new", "1001").await?;
+
+    let old_linked = env
+        .contract
+        .view("is_linked")
+        .args_json(json!({"platform": "Twitter", "handle": "alice123"}))
+        .await?
+        .json::<bool>()?;
+    assert!(!old_linked, "Old handle should be unlinked");
+
+    let renamed = env
+        .contract
+        .view("get_renamed_handle")
+        .args_json(json!({"platform": "Twitter", "handle": "alice123"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(renamed["handle"], "alice_new");
+
+    // A tip to the released handle is for its next owner, so the alias is dropped
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "alice123",
+        NearToken::from_near(1),
+        json!(null),
+    )
+    .await?;
+    let renamed = env
+        .contract
+        .view("get_renamed_handle")
+        .args_json(json!({"platform": "Twitter", "handle": "alice123"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(renamed.is_null(), "Alias should be dropped");
+
+    let outcome = env
+        .alice
+        .call(env.contract.id(), "claim")
+        .args_json(json!({"platform": "Twitter", "handle": "alice123"}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(
+        outcome.is_failure(),
+        "The renamed user can't collect tips made after the rename"
+    );
+
+    Ok(())
+}
+
+// **Expired tips: reclaim_all returns the tipper's tips, sweep_expired settles the rest**
+#[tokio::test]
+async fn test_reclaim_all_and_sweep() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let day = 24 * 60 * 60 * 1_000_000_000u64;
+    let terms = json!({"expiry": {"After": day}});
+
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "expired1",
+        NearToken::from_near(1),
+        terms.clone(),
+    )
+    .await?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "expired2",
+        NearToken::from_near(1),
+        terms.clone(),
+    )
+    .await?;
+    // Carol tips anonymously, so sweeping her tip mustn't reveal her
+    env.carol
+        .call(env.contract.id(), "tip_near")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": "expired3",
+            "anonymous": true,
+            "terms": terms
+        }))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    let bob_claims = [
+        pending_claim_id(&env.contract, "expired1").await?,
+        pending_claim_id(&env.contract, "expired2").await?,
+    ];
+    let carol_claim = pending_claim_id(&env.contract, "expired3").await?;
+
+    // Shortest grace period, so the claims become sweepable a week after expiring
+    env.owner
+        .call(env.contract.id(), "set_sweep_grace_period")
+        .args_json(json!({"grace_period": 7 * day}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    for _ in 0..100 {
+        env.worker.fast_forward(10000).await?;
+    }
+
+    let initial_bob_balance = env.bob.view_account().await?.balance;
+    env.bob
+        .call(env.contract.id(), "reclaim_all")
+        .args_json(json!({}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let final_bob_balance = env.bob.view_account().await?.balance;
+    assert!(
+        final_bob_balance > initial_bob_balance,
+        "Bob should reclaim both tips"
+    );
+    for claim_id in bob_claims {
+        assert_eq!(claim_status(&env.contract, claim_id).await?, "Reclaimed");
+    }
+
+    // Anyone can sweep; the default destination returns Carol's tip to her
+    let outcome = env
+        .alice
+        .call(env.contract.id(), "sweep_expired")
+        .args_json(json!({}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert!(
+        outcome
+            .logs()
+            .iter()
+            .all(|log| !log.contains(env.carol.id().as_str())),
+        "Sweep events shouldn't name an anonymous tipper"
+    );
+    let swept = outcome.json::<u64>()?;
+    assert_eq!(swept, 1, "Only Carol's tip is left to sweep");
+    assert_eq!(claim_status(&env.contract, carol_claim).await?, "Reclaimed");
+
+    Ok(())
+}
+
+// **Quadratic funding: settled tips earn the handle a share of the matching pool**
+#[tokio::test]
+async fn test_quadratic_funding_round() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "alice123";
+
+    let now = env.worker.view_block().await?.timestamp();
+    let round_id = env
+        .owner
+        .call(env.contract.id(), "create_round")
+        .args_json(json!({
+            "token_id": null,
+            "start": now,
+            "end": now + 24 * 60 * 60 * 1_000_000_000u64
+        }))
+        .deposit(NearToken::from_near(5))
+        .transact()
+        .await?
+        .into_result()?
+        .json::<u64>()?;
+
+    // Tips count when they're made, even while the handle is still unlinked
+    tip_near(
+        &env.contract,
+        &env.bob,
+        handle,
+        NearToken::from_near(1),
+        json!(null),
+    )
+    .await?;
+    tip_near(
+        &env.contract,
+        &env.carol,
+        handle,
+        NearToken::from_near(1),
+        json!(null),
+    )
+    .await?;
+
+    // A cancelled tip is taken back out of the round
+    tip_near(
+        &env.contract,
+        &env.carol,
+        handle,
+        NearToken::from_near(1),
+        json!({"cancellable": true}),
+    )
+    .await?;
+    let claims = env
+        .contract
+        .view("get_pending_claims")
+        .args_json(json!({"platform": "Twitter", "handle": handle, "limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    env.carol
+        .call(env.contract.id(), "cancel_tip")
+        .args_json(json!({"claim_id": claims["items"][2]["id"]}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let round_match = env
+        .contract
+        .view("get_round_match")
+        .args_json(json!({"round_id": round_id, "platform": "Twitter", "handle": handle}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(round_match[0]["contributors"], 2);
+    assert_eq!(
+        round_match[0]["total"],
+        NearToken::from_near(2).as_yoctonear().to_string()
+    );
+
+    for _ in 0..100 {
+        env.worker.fast_forward(10000).await?;
+    }
+
+    // Linking and claiming after the round ended still collects the matched tips
+    link(&env.contract, &env.alice, handle, "1001").await?;
+
+    env.bob
+        .call(env.contract.id(), "finalize_round")
+        .args_json(json!({"round_id": round_id}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let round = env
+        .contract
+        .view("get_round")
+        .args_json(json!({"round_id": round_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(round["finalized"], true);
+    assert_eq!(
+        round["distributed"],
+        NearToken::from_near(5).as_yoctonear().to_string()
+    );
+
+    // The match is paid out as a pending claim for the handle
+    let initial_alice_balance = env.alice.view_account().await?.balance;
+    env.alice
+        .call(env.contract.id(), "claim")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let final_alice_balance = env.alice.view_account().await?.balance;
+    assert!(
+        final_alice_balance > initial_alice_balance,
+        "Alice should receive the tips and the matching funds"
+    );
+
+    Ok(())
+}
+
+// **Bounties: held for the arbiter, then claimable or refunded**
+#[tokio::test]
+async fn test_bounty_approval_and_rejection() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "alice123";
+    link(&env.contract, &env.alice, handle, "1001").await?;
+
+    for _ in 0..2 {
+        env.bob
+            .call(env.contract.id(), "tip_near")
+            .args_json(json!({
+                "platform": "Twitter",
+                "handle": handle,
+                "arbiter": env.carol.id()
+            }))
+            .deposit(NearToken::from_near(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+    }
+    let claims = handle_claims(&env.contract, handle).await?;
+    assert_eq!(
+        claims.len(),
+        2,
+        "Bounties are held even for a linked handle"
+    );
+    let approved = claims[0]["id"].as_u64().unwrap();
+    let rejected = claims[1]["id"].as_u64().unwrap();
+    assert_eq!(
+        claim_status(&env.contract, approved).await?,
+        "AwaitingApproval"
+    );
+
+    // Only the arbiter decides
+    let result = env
+        .bob
+        .call(env.contract.id(), "approve_bounty")
+        .args_json(json!({"claim_id": approved}))
+        .transact()
+        .await?;
+    assert!(
+        result.is_failure(),
+        "The tipper can't approve its own bounty"
+    );
+
+    env.carol
+        .call(env.contract.id(), "approve_bounty")
+        .args_json(json!({"claim_id": approved}))
+        .transact()
+        .await?
+        .into_result()?;
+    env.carol
+        .call(env.contract.id(), "reject_bounty")
+        .args_json(json!({"claim_id": rejected}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(claim_status(&env.contract, rejected).await?, "Reclaimed");
+
+    env.alice
+        .call(env.contract.id(), "claim")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(claim_status(&env.contract, approved).await?, "Claimed");
+
+    Ok(())
+}
+
+// **Campaigns: kept when they succeed, refunded when an all-or-nothing goal is missed**
+#[tokio::test]
+async fn test_campaign_claim_and_refund() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "alice123";
+    link(&env.contract, &env.alice, handle, "1001").await?;
+
+    let deadline = env.worker.view_block().await?.timestamp() + 10_000_000_000;
+    let mut campaign_ids = vec![];
+    for mode in ["KeepWhatYouRaise", "AllOrNothing"] {
+        let campaign_id = env
+            .alice
+            .call(env.contract.id(), "create_campaign")
+            .args_json(json!({
+                "platform": "Twitter",
+                "handle": handle,
+                "campaign": {
+                    "title": "New album",
+                    "goal": NearToken::from_near(10).as_yoctonear().to_string(),
+                    "deadline": deadline,
+                    "mode": mode
+                }
+            }))
+            .transact()
+            .await?
+            .json::<u64>()?;
+        env.bob
+            .call(env.contract.id(), "tip_near")
+            .args_json(json!({
+                "platform": "Twitter",
+                "handle": handle,
+                "campaign_id": campaign_id
+            }))
+            .deposit(NearToken::from_near(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        campaign_ids.push(campaign_id);
+    }
+
+    let mut contributions = vec![];
+    for campaign_id in &campaign_ids {
+        let page = env
+            .contract
+            .view("get_campaign_contributions")
+            .args_json(json!({"campaign_id": campaign_id, "limit": 10}))
+            .await?
+            .json::<serde_json::Value>()?;
+        contributions.push(page["items"][0]["id"].as_u64().unwrap());
+    }
+    assert!(
+        handle_claims(&env.contract, handle).await?.is_empty(),
+        "Contributions are only claimable through their campaign"
+    );
+
+    tokio::time::sleep(std::time::Duration::from_secs(12)).await;
+
+    let result = env
+        .alice
+        .call(env.contract.id(), "claim_campaign")
+        .args_json(json!({"campaign_id": campaign_ids[1]}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(
+        result.is_failure(),
+        "A missed all-or-nothing goal can't be claimed"
+    );
+
+    env.alice
+        .call(env.contract.id(), "claim_campaign")
+        .args_json(json!({"campaign_id": campaign_ids[0]}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(
+        claim_status(&env.contract, contributions[0]).await?,
+        "Claimed"
+    );
+
+    env.bob
+        .call(env.contract.id(), "refund_contribution")
+        .args_json(json!({"claim_id": contributions[1]}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(
+        claim_status(&env.contract, contributions[1]).await?,
+        "Reclaimed"
+    );
+
+    Ok(())
+}
+
+// **Roles: granted and revoked by the owner, checked on admin calls**
+#[tokio::test]
+async fn test_roles_gate_admin_calls() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+
+    let result = env
+        .carol
+        .call(env.contract.id(), "pause")
+        .args_json(json!({}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Carol can't pause without the role");
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "grant_role")
+        .args_json(json!({"role": "Pauser", "account_id": env.carol.id()}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Only the owner grants roles");
+
+    env.owner
+        .call(env.contract.id(), "grant_role")
+        .args_json(json!({"role": "Pauser", "account_id": env.carol.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+    let has_role = env
+        .contract
+        .view("has_role")
+        .args_json(json!({"role": "Pauser", "account_id": env.carol.id()}))
+        .await?
+        .json::<bool>()?;
+    assert!(has_role);
+
+    env.carol
+        .call(env.contract.id(), "pause")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+    env.carol
+        .call(env.contract.id(), "unpause")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    env.owner
+        .call(env.contract.id(), "revoke_role")
+        .args_json(json!({"role": "Pauser", "account_id": env.carol.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+    let result = env
+        .carol
+        .call(env.contract.id(), "pause")
+        .args_json(json!({}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "A revoked role no longer applies");
+
+    Ok(())
+}
+
+// **Ownership: proposed, accepted only by the new owner after the delay, or cancelled**
+#[tokio::test]
+async fn test_ownership_transfer_is_two_step() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+
+    env.owner
+        .call(env.contract.id(), "propose_owner")
+        .args_json(json!({"new_owner": env.carol.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+    let pending = env
+        .contract
+        .view("get_pending_owner")
+        .args_json(json!({}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(pending["account_id"], json!(env.carol.id()));
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "accept_ownership")
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Only the proposed owner can accept");
+    let result = env
+        .carol
+        .call(env.contract.id(), "accept_ownership")
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "The transfer delay hasn't passed");
+
+    env.owner
+        .call(env.contract.id(), "cancel_ownership_transfer")
+        .transact()
+        .await?
+        .into_result()?;
+    let pending = env
+        .contract
+        .view("get_pending_owner")
+        .args_json(json!({}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(pending.is_null());
+    let owner = env
+        .contract
+        .view("get_owner")
+        .args_json(json!({}))
+        .await?
+        .json::<AccountId>()?;
+    assert_eq!(&owner, env.owner.id());
+
+    Ok(())
+}
+
+// **Timelock: admin changes wait out the delay and can be cancelled meanwhile**
+#[tokio::test]
+async fn test_admin_actions_are_timelocked() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "set_reclaim_contract")
+        .args_json(json!({"contract_id": env.bob.id()}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Bob has no platform manager role");
+
+    let action_id = env
+        .owner
+        .call(env.contract.id(), "set_reclaim_contract")
+        .args_json(json!({"contract_id": env.bob.id()}))
+        .transact()
+        .await?
+        .json::<u64>()?;
+    let queued = env
+        .contract
+        .view("get_queued_action")
+        .args_json(json!({"action_id": action_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    let delay = env
+        .contract
+        .view("get_admin_action_delay")
+        .args_json(json!({}))
+        .await?
+        .json::<u64>()?;
+    assert_eq!(
+        queued["executable_at"].as_u64().unwrap(),
+        queued["queued_at"].as_u64().unwrap() + delay
+    );
+
+    let result = env
+        .owner
+        .call(env.contract.id(), "execute_admin_action")
+        .args_json(json!({"action_id": action_id}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "The action is still timelocked");
+
+    env.owner
+        .call(env.contract.id(), "cancel_admin_action")
+        .args_json(json!({"action_id": action_id}))
+        .transact()
+        .await?
+        .into_result()?;
+    let queued = env
+        .contract
+        .view("get_queued_action")
+        .args_json(json!({"action_id": action_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(queued.is_null());
+    let reclaim_contract = env
+        .contract
+        .view("get_reclaim_contract")
+        .args_json(json!({}))
+        .await?
+        .json::<AccountId>()?;
+    assert_ne!(&reclaim_contract, env.bob.id());
+
+    Ok(())
+}
+
+// **Pauses: one operation can be paused while the rest keep working**
+#[tokio::test]
+async fn test_pause_single_operation() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+
+    env.owner
+        .call(env.contract.id(), "pause")
+        .args_json(json!({"operations": ["TipNear"]}))
+        .transact()
+        .await?
+        .into_result()?;
+    let paused = env
+        .contract
+        .view("get_paused_operations")
+        .args_json(json!({}))
+        .await?
+        .json::<Vec<String>>()?;
+    assert_eq!(paused, vec!["TipNear".to_string()]);
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "tip_near")
+        .args_json(json!({"platform": "Twitter", "handle": "alice123"}))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "NEAR tips are paused");
+    link(&env.contract, &env.alice, "alice123", "1001").await?;
+
+    env.owner
+        .call(env.contract.id(), "unpause")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "alice123",
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// **Rescue: the guardian can only move NEAR that no claim is owed**
+#[tokio::test]
+async fn test_rescue_is_limited_to_surplus() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "dave",
+        NearToken::from_near(2),
+        json!({}),
+    )
+    .await?;
+
+    let surplus: u128 = escrow_report(&env.contract).await?["surplus"]
+        .as_str()
+        .unwrap()
+        .parse()?;
+    let result = env
+        .owner
+        .call(env.contract.id(), "rescue_near")
+        .args_json(json!({
+            "amount": (surplus + NearToken::from_near(1).as_yoctonear()).to_string(),
+            "receiver_id": env.owner.id()
+        }))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Escrowed tips can't be rescued");
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "rescue_near")
+        .args_json(json!({"amount": (surplus / 2).to_string(), "receiver_id": env.bob.id()}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Only the guardian can rescue");
+
+    env.owner
+        .call(env.contract.id(), "rescue_near")
+        .args_json(json!({"amount": (surplus / 2).to_string(), "receiver_id": env.owner.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(escrow_report(&env.contract).await?["solvent"], json!(true));
+
+    Ok(())
+}
+
+// **Escrow invariant: only tips held by the contract are escrowed, and it stays solvent**
+#[tokio::test]
+async fn test_escrow_invariant_holds() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    link(&env.contract, &env.alice, "alice123", "1001").await?;
+
+    let escrowed_before = escrowed_near(&env.contract).await?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "alice123",
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+    assert_eq!(
+        escrowed_near(&env.contract).await?,
+        escrowed_before,
+        "Direct tips pass straight through"
+    );
+
+    let report = env
+        .carol
+        .call(env.contract.id(), "audit_solvency")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(report["solvent"], json!(true));
+    assert_eq!(
+        report["escrowed"],
+        json!(escrowed_near(&env.contract).await?.to_string())
+    );
+    assert_eq!(report["deficit"], json!("0"));
+
+    Ok(())
+}
+
+// **Blocklists: blocked handles can't be tipped and blocked accounts can't tip**
+#[tokio::test]
+async fn test_blocklists_stop_tips() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+
+    env.owner
+        .call(env.contract.id(), "block_handle")
+        .args_json(json!({"platform": "Twitter", "handle": "spam123", "reason": "impersonation"}))
+        .transact()
+        .await?
+        .into_result()?;
+    env.owner
+        .call(env.contract.id(), "block_account")
+        .args_json(json!({"account_id": env.carol.id(), "reason": "abuse"}))
+        .transact()
+        .await?
+        .into_result()?;
+    let block = env
+        .contract
+        .view("get_handle_block")
+        .args_json(json!({"platform": "Twitter", "handle": "spam123"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(block["reason"], json!("impersonation"));
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "tip_near")
+        .args_json(json!({"platform": "Twitter", "handle": "spam123"}))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Blocked handles can't be tipped");
+    let result = env
+        .carol
+        .call(env.contract.id(), "tip_near")
+        .args_json(json!({"platform": "Twitter", "handle": "dave"}))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Blocked accounts can't tip");
+
+    env.owner
+        .call(env.contract.id(), "unblock_handle")
+        .args_json(json!({"platform": "Twitter", "handle": "spam123", "reason": "verified"}))
+        .transact()
+        .await?
+        .into_result()?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "spam123",
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// **Link expiry: stale links hold tips as claims until they are refreshed**
+#[tokio::test]
+async fn test_stale_link_must_be_refreshed() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let handle = "alice123";
+    env.owner
+        .call(env.contract.id(), "set_link_validity_period")
+        .args_json(json!({"period": 3_000_000_000u64}))
+        .transact()
+        .await?
+        .into_result()?;
+    link(&env.contract, &env.alice, handle, "1001").await?;
+
+    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    let stale = env
+        .contract
+        .view("is_link_stale")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .await?
+        .json::<bool>()?;
+    assert!(stale, "The link should have expired");
+
+    tip_near(
+        &env.contract,
+        &env.bob,
+        handle,
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+    let claim_id = pending_claim_id(&env.contract, handle).await?;
+    let result = env
+        .alice
+        .call(env.contract.id(), "claim")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Stale links can't claim");
+
+    // Refreshed links get the validity period in force at refresh time
+    env.owner
+        .call(env.contract.id(), "set_link_validity_period")
+        .args_json(json!({"period": 3_600_000_000_000u64}))
+        .transact()
+        .await?
+        .into_result()?;
+    env.alice
+        .call(env.contract.id(), "refresh_link")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "proof": create_mock_proof_for_user(handle, "Twitter", "1001")
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    env.alice
+        .call(env.contract.id(), "claim")
+        .args_json(json!({"platform": "Twitter", "handle": handle}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(claim_status(&env.contract, claim_id).await?, "Claimed");
+
+    Ok(())
+}
+
+// **Reverse indexes: handles by account and claims by tipper**
+#[tokio::test]
+async fn test_reverse_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    link(&env.contract, &env.alice, "alice123", "1001").await?;
+    link(&env.contract, &env.alice, "alice_alt", "1002").await?;
+
+    let handles = env
+        .contract
+        .view("get_handles_by_account")
+        .args_json(json!({"account_id": env.alice.id(), "limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(
+        handles["items"],
+        json!(["twitter:alice123", "twitter:alice_alt"])
+    );
+
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "dave",
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+    env.bob
+        .call(env.contract.id(), "tip_near")
+        .args_json(json!({"platform": "Twitter", "handle": "dave", "anonymous": true}))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let public = env
+        .contract
+        .view("get_claims_by_tipper")
+        .args_json(json!({"tipper": env.bob.id(), "limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(public["items"].as_array().unwrap().len(), 1);
+
+    let mine = env
+        .bob
+        .call(env.contract.id(), "get_my_tips")
+        .args_json(json!({"limit": 10}))
+        .transact()
+        .await?
+        .json::<serde_json::Value>()?;
+    let mine = mine["items"].as_array().unwrap();
+    assert_eq!(mine.len(), 2, "Bob sees his anonymous tip too");
+    assert!(mine
+        .iter()
+        .all(|claim| claim["tipper"] == json!(env.bob.id())));
+
+    Ok(())
+}
+
+// **Pagination: pages follow the returned cursor to the end**
+#[tokio::test]
+async fn test_claims_are_paginated() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    for _ in 0..3 {
+        tip_near(
+            &env.contract,
+            &env.bob,
+            "dave",
+            NearToken::from_near(1),
+            json!({}),
+        )
+        .await?;
+    }
+
+    let first = env
+        .contract
+        .view("get_all_claims_for_handle")
+        .args_json(json!({"platform": "Twitter", "handle": "dave", "limit": 2}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(first["items"].as_array().unwrap().len(), 2);
+    assert!(!first["next_cursor"].is_null());
+
+    let second = env
+        .contract
+        .view("get_all_claims_for_handle")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": "dave",
+            "cursor": first["next_cursor"],
+            "limit": 2
+        }))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(second["items"].as_array().unwrap().len(), 1);
+    assert!(second["next_cursor"].is_null());
+    assert_ne!(first["items"][0]["id"], second["items"][0]["id"]);
+
+    Ok(())
+}
+
+// **Claim views: typed assets, with the original flat format kept under `_v1`**
+#[tokio::test]
+async fn test_typed_and_v1_claim_views() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "dave",
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+    let claim_id = pending_claim_id(&env.contract, "dave").await?;
+
+    let claim = env
+        .contract
+        .view("get_claim_by_id")
+        .args_json(json!({"claim_id": claim_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(
+        claim["asset"],
+        json!({"Near": {"symbol": "NEAR", "decimals": 24}})
+    );
+    assert_eq!(claim["tipper"], json!(env.bob.id()));
+
+    let claim_v1 = env
+        .contract
+        .view("get_claim_by_id_v1")
+        .args_json(json!({"claim_id": claim_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(claim_v1["claim_type"], json!("Near"));
+    assert_eq!(claim_v1["claimed"], json!(false));
+    assert_eq!(claim_v1["amount"], claim["amount"]);
+
+    let pending_v1 = env
+        .contract
+        .view("get_pending_claims_v1")
+        .args_json(json!({"platform": "Twitter", "handle": "dave", "from_index": 0, "limit": 10}))
+        .await?
+        .json::<Vec<serde_json::Value>>()?;
+    assert_eq!(pending_v1.len(), 1);
+    assert_eq!(pending_v1[0]["id"], json!(claim_id));
+
+    Ok(())
+}
+
+// **Expiry bounds: tippers choose an expiry within the token's configuration**
+#[tokio::test]
+async fn test_tip_expiry_bounds() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let hour = 3_600_000_000_000u64;
+
+    for expiry in [json!({"After": hour}), json!("Never")] {
+        let result = env
+            .bob
+            .call(env.contract.id(), "tip_near")
+            .args_json(json!({
+                "platform": "Twitter",
+                "handle": "dave",
+                "terms": {"expiry": expiry}
+            }))
+            .deposit(NearToken::from_near(1))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(result.is_failure(), "Expiry {} is out of bounds", expiry);
+    }
+
+    env.owner
+        .call(env.contract.id(), "set_token_expiry")
+        .args_json(json!({
+            "expiry": {
+                "default_period": 30 * 24 * hour,
+                "min_period": hour,
+                "max_period": 60 * 24 * hour,
+                "allow_never": true
+            }
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "dave",
+        NearToken::from_near(1),
+        json!({"expiry": "Never"}),
+    )
+    .await?;
+
+    let claim_id = pending_claim_id(&env.contract, "dave").await?;
+    let claim = env
+        .contract
+        .view("get_claim_by_id")
+        .args_json(json!({"claim_id": claim_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(claim["expires_at"], json!(u64::MAX));
+
+    Ok(())
+}
+
+// **Extend and cancel: the tipper can push an expiry out or take back a cancellable tip**
+#[tokio::test]
+async fn test_extend_and_cancel_tip() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let day = 24 * 3_600_000_000_000u64;
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "dave",
+        NearToken::from_near(1),
+        json!({}),
+    )
+    .await?;
+    let claim_id = pending_claim_id(&env.contract, "dave").await?;
+    let claim = env
+        .contract
+        .view("get_claim_by_id")
+        .args_json(json!({"claim_id": claim_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    let expires_at = claim["expires_at"].as_u64().unwrap();
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "extend_claim")
+        .args_json(json!({"claim_id": claim_id, "new_expiry": expires_at + 400 * day}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Past the token's max period");
+    let result = env
+        .carol
+        .call(env.contract.id(), "extend_claim")
+        .args_json(json!({"claim_id": claim_id, "new_expiry": expires_at + day}))
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Only the tipper can extend");
+    env.bob
+        .call(env.contract.id(), "extend_claim")
+        .args_json(json!({"claim_id": claim_id, "new_expiry": expires_at + day}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "cancel_tip")
+        .args_json(json!({"claim_id": claim_id}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "The tip wasn't made cancellable");
+
+    tip_near(
+        &env.contract,
+        &env.bob,
+        "erin",
+        NearToken::from_near(1),
+        json!({"cancellable": true}),
+    )
+    .await?;
+    let cancellable_id = pending_claim_id(&env.contract, "erin").await?;
+    env.bob
+        .call(env.contract.id(), "cancel_tip")
+        .args_json(json!({"claim_id": cancellable_id}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(
+        claim_status(&env.contract, cancellable_id).await?,
+        "Reclaimed"
+    );
+
+    Ok(())
+}
+
+// **Reclaim all: only expired tips are returned, up to the limit per call**
+#[tokio::test]
+async fn test_reclaim_all_respects_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let env = setup().await?;
+    let day = 24 * 60 * 60 * 1_000_000_000u64;
+    for handle in ["expired1", "expired2"] {
+        tip_near(
+            &env.contract,
+            &env.bob,
+            handle,
+            NearToken::from_near(1),
+            json!({"expiry": {"After": day}}),
+        )
+        .await?;
+    }
+    let claims = [
+        pending_claim_id(&env.contract, "expired1").await?,
+        pending_claim_id(&env.contract, "expired2").await?,
+    ];
+
+    let result = env
+        .bob
+        .call(env.contract.id(), "reclaim_all")
+        .args_json(json!({}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Nothing has expired yet");
+
+    for _ in 0..100 {
+        env.worker.fast_forward(10000).await?;
+    }
+
+    env.bob
+        .call(env.contract.id(), "reclaim_all")
+        .args_json(json!({"limit": 1}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(claim_status(&env.contract, claims[0]).await?, "Reclaimed");
+    assert_eq!(claim_status(&env.contract, claims[1]).await?, "Pending");
+
+    env.bob
+        .call(env.contract.id(), "reclaim_all")
+        .args_json(json!({}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(claim_status(&env.contract, claims[1]).await?, "Reclaimed");
+
+    Ok(())
+}
+
+// **Settlements: a failed refund is kept for a retry instead of being lost**
+#[tokio::test]
+async fn test_failed_settlement_is_recorded() -> Result<(), Box<dyn std::error::Error>> {
+    let TestEnv {
+        contract,
+        owner,
+        bob,
+        carol,
+        ..
+    } = setup().await?;
+    bob.call(contract.id(), "tip_near")
+        .args_json(json!({"platform": "Twitter", "handle": "dave", "arbiter": carol.id()}))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    let claim_id = handle_claims(&contract, "dave").await?[0]["id"]
+        .as_u64()
+        .unwrap();
+
+    // The refund can't reach a deleted account
+    let bob_id = bob.id().clone();
+    bob.delete_account(owner.id()).await?.into_result()?;
+    carol
+        .call(contract.id(), "reject_bounty")
+        .args_json(json!({"claim_id": claim_id}))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    assert_eq!(claim_status(&contract, claim_id).await?, "Settling");
+    let failed = contract
+        .view("get_failed_settlements")
+        .args_json(json!({"limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    let settlement = &failed["items"][0];
+    assert_eq!(settlement["claim_id"], json!(claim_id));
+    assert_eq!(settlement["beneficiary"], json!(bob_id));
+    assert_eq!(settlement["failed_attempts"], json!(1));
+    assert_eq!(settlement["previous_status"], json!("AwaitingApproval"));
+
+    let result = carol
+        .call(contract.id(), "retry_settlement")
+        .args_json(json!({"claim_id": claim_id}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(
+        result.is_failure(),
+        "Only the beneficiary or tipper can retry"
+    );
+
+    Ok(())
+}
+
+struct TestEnv {
+    worker: near_workspaces::Worker<near_workspaces::network::Sandbox>,
+    contract: Contract,
+    owner: Account,
+    alice: Account,
+    bob: Account,
+    carol: Account,
+}
+
+// Deploys the contract with an owner and three funded users. Bob and Carol cover tip storage
+async fn setup() -> Result<TestEnv, Box<dyn std::error::Error>> {
+    let worker = near_workspaces::sandbox().await?;
+    let root = worker.root_account()?;
+
+    let owner = create_subaccount(&root, "owner").await?;
+    let alice = create_subaccount(&root, "alice").await?;
+    let bob = create_subaccount(&root, "bob").await?;
+    let carol = create_subaccount(&root, "carol").await?;
+
+    let reclaim_contract = deploy_reclaim_mock(&worker).await?;
+    let contract = deploy_claim_protocol(&worker, reclaim_contract.id(), owner.id()).await?;
+
+    for tipper in [&bob, &carol] {
+        tipper
+            .call(contract.id(), "storage_deposit")
+            .args_json(json!({}))
+            .deposit(NearToken::from_near(1))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    Ok(TestEnv {
+        worker,
+        contract,
+        owner,
+        alice,
+        bob,
+        carol,
+    })
+}
+
+async fn link(
+    contract: &Contract,
+    account: &Account,
+    handle: &str,
+    user_id: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let proof = create_mock_proof_for_user(handle, "Twitter", user_id);
+    link_with_proof(contract, account, handle, proof).await
+}
+
+async fn link_with_proof(
+    contract: &Contract,
+    account: &Account,
+    handle: &str,
+    proof: serde_json::Value,
+) -> Result<(), Box<dyn std::error::Error>> {
+    account
+        .call(contract.id(), "link_account")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "proof": proof
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn tip_near(
+    contract: &Contract,
+    tipper: &Account,
+    handle: &str,
+    amount: NearToken,
+    terms: serde_json::Value,
+) -> Result<(), Box<dyn std::error::Error>> {
+    tipper
+        .call(contract.id(), "tip_near")
+        .args_json(json!({
+            "platform": "Twitter",
+            "handle": handle,
+            "terms": terms
+        }))
+        .deposit(amount)
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn pending_claim_id(
+    contract: &Contract,
+    handle: &str,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let claims = contract
+        .view("get_pending_claims")
+        .args_json(json!({"platform": "Twitter", "handle": handle, "limit": 10}))
+        .await?
+        .json::<serde_json::Value>()?;
+    Ok(claims["items"][0]["id"]
+        .as_u64()
+        .ok_or("No pending claim for handle")?)
+}
+
+async fn claim_status(
+    contract: &Contract,
+    claim_id: u64,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let claim = contract
+        .view("get_claim_by_id")
+        .args_json(json!({"claim_id": claim_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    Ok(claim["status"].as_str().unwrap_or_default().to_string())
+}
+
+async fn escrowed_near(contract: &Contract) -> Result<u128, Box<dyn std::error::Error>> {
+    let escrowed = contract
+        .view("get_escrowed")
+        .args_json(json!({"token_id": "near"}))
+        .await?
+        .json::<U128>()?;
+    Ok(escrowed.0)
+}
+
+async fn handle_claims(
+    contract: &Contract,
+    handle: &str,
+) -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+    let claims = contract
+        .view("get_all_claims_for_handle")
+        .args_json(json!({"platform": "Twitter", "handle": handle, "limit": 50}))
+        .await?
+        .json::<serde_json::Value>()?;
+    Ok(claims["items"].as_array().cloned().unwrap_or_default())
+}
+
+async fn escrow_report(
+    contract: &Contract,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    Ok(contract
+        .view("get_escrow_invariant")
+        .args_json(json!({}))
+        .await?
+        .json::<serde_json::Value>()?)
+}
+
+// Helper to create a mock proof structure
+fn create_mock_proof(handle: &str, platform: &str) -> serde_json::Value {
+    create_mock_proof_for_user(handle, platform, handle)
+}
+
+// Mock proof for a platform user, whose id survives handle renames
+fn create_mock_proof_for_user(handle: &str, platform: &str, user_id: &str) -> serde_json::Value {
+    let timestamp = Utc::now().timestamp() as u64;
+    json!({
+        "claimInfo": {
+            "provider": platform,
+            "parameters": handle,
+            "context": json!({
+                "extractedParameters": {"screen_name": handle, "user_id": user_id}
+            }).to_string()
         },
-        "signed_claim": {
+        "signedClaim": {
             "claim": {
-                "identifier": format!("test-identifier-{}", handle),
+                "identifier": format!("test-identifier-{}-{}-{}", handle, user_id, timestamp),
                 "owner": "test-owner",
                 "epoch": 1,
-                "timestampS": Utc::now().timestamp() as u64
+                "timestampS": timestamp
             },
             "signatures": ["test-signature"]
         }